@@ -0,0 +1,92 @@
+use std::num::NonZeroUsize;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ndarray::prelude::*;
+use owm::optimize;
+use owm_problem::{
+    encoding::{BitCode, Decode, Decoder},
+    objective::{Problem, ProblemParams},
+    Adjacency, AreaRatio, AspectRatio, GridCount, Length, MainAnchor, ReadingOrder, Rect, Size,
+    SymmetryAxes, VisibleFraction, Weight, Weights,
+};
+
+/// Compare how long one layout takes to converge
+/// and how good it is
+/// for each bit code.
+fn bit_code(c: &mut Criterion) {
+    let container = Size::new_checked(1920, 1080);
+    let mut group = c.benchmark_group("bit_code");
+    group.sample_size(10);
+    for count in [3, 6] {
+        let count = NonZeroUsize::new(count).unwrap();
+        for bit_code in [BitCode::Binary, BitCode::Gray] {
+            let decoder = Decoder::new(
+                vec![Size::new_checked(320, 180); count.get()],
+                vec![container; count.get()],
+                Length::Pixels(128),
+                bit_code,
+                Vec::new(),
+                container,
+            );
+            let problem = problem(container, count);
+            let probabilities = Array1::from_elem(decoder.bits(), 0.5);
+            println!(
+                "{bit_code}/{count} score: {}",
+                problem.evaluate(&optimize(&decoder, &problem, probabilities.clone()))
+            );
+            group.bench_function(format!("{bit_code}/{count}"), |b| {
+                b.iter(|| optimize(&decoder, &problem, probabilities.clone()))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Return a problem for `count` windows
+/// with consistency left out,
+/// so no previous layout is needed.
+fn problem(container: Size, count: NonZeroUsize) -> Problem {
+    let weight = |x| Weight::new(x).unwrap();
+    Problem::new(ProblemParams {
+        weights: Weights {
+            gaps_weight: weight(5.0),
+            overlap_weight: weight(6.0),
+            area_ratios_weight: weight(1.5),
+            aspect_ratios_weight: weight(3.0),
+            adjacent_close_weight: weight(0.5),
+            reading_order_weight: weight(0.5),
+            center_main_weight: weight(1.5),
+            consistency_weight: weight(0.0),
+            symmetry_weight: weight(0.0),
+            edge_alignment_weight: weight(0.0),
+            reserved_weight: weight(6.0),
+            grid_weight: weight(0.0),
+            sliceable_weight: weight(0.0),
+            group_equality_weight: weight(0.0),
+            readable_weight: weight(3.0),
+            hidden_weight: weight(0.0),
+        },
+        constraints: Vec::new(),
+        area_ratios: vec![AreaRatio::new(2.0).unwrap()],
+        aspect_ratios: vec![AspectRatio::new(1.77777).unwrap()],
+        equal_groups: Vec::new(),
+        main_anchor: MainAnchor::Center,
+        main: 0,
+        adjacency: Adjacency::Corners,
+        reading_order: ReadingOrder::default(),
+        symmetry_axes: SymmetryAxes::Horizontal,
+        edge_alignment_tolerance: 16,
+        grid_columns: GridCount::Any,
+        grid_rows: GridCount::Any,
+        readable_sizes: vec![Size::new_checked(1, 1); count.get()],
+        min_visible: VisibleFraction::new(0.25).unwrap(),
+        min_sizes: vec![Size::new_checked(320, 180); count.get()],
+        max_size: container,
+        container,
+        reserved: Vec::new(),
+        prev_layout: vec![Rect::new(0, 0, container.width, container.height); count.get() - 1],
+    })
+}
+
+criterion_group!(benches, bit_code);
+criterion_main!(benches);
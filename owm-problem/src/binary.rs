@@ -1,5 +1,3 @@
-#![allow(dead_code)]
-
 use num_traits::{pow, One, Zero};
 use std::ops::{Add, Div, Mul, RangeInclusive, Sub};
 
@@ -11,25 +9,26 @@ use std::ops::{Add, Div, Mul, RangeInclusive, Sub};
 ///
 /// ```ignore
 /// // It returns lower bound for empty arrays:
-/// assert_eq!(ToFracLe::new(1.0..=2.0, 0)::decode(vec![]), 1.);
+/// assert_eq!(ToFracLE::new(1.0..=2.0, 0).decode(vec![]), 1.);
 ///
 /// // It returns lower bound when all bits are false:
-/// assert_eq!(ToFracLe::new(0.0..=1.0, 1)::decode(vec![false]), 0.);
-/// assert_eq!(ToFracLe::new(1.0..=2.0, 2)::decode(vec![false, false]), 1.);
+/// assert_eq!(ToFracLE::new(0.0..=1.0, 1).decode(vec![false]), 0.);
+/// assert_eq!(ToFracLE::new(1.0..=2.0, 2).decode(vec![false, false]), 1.);
 ///
 /// // It returns upper bound when all bits are true:
-/// assert_eq!(ToFracLe::new(0.0..=1.0, 1)::decode(vec![true]), 1.);
-/// assert_eq!(ToFracLe::new(1.0..=2.0, 2)::decode(vec![true, true]), 2.);
+/// assert_eq!(ToFracLE::new(0.0..=1.0, 1).decode(vec![true]), 1.);
+/// assert_eq!(ToFracLE::new(1.0..=2.0, 2).decode(vec![true, true]), 2.);
 ///
 /// // It returns a number between lower and upper bound when some bits are true:
-/// assert_eq!(ToFracLe::new(1.0..=4.0, 2)::decode(vec![true, false]), 2.);
-/// assert_eq!(ToFracLe::new(1.0..=4.0, 2)::decode(vec![false, true]), 3.);
+/// assert_eq!(ToFracLE::new(1.0..=4.0, 2).decode(vec![true, false]), 2.);
+/// assert_eq!(ToFracLE::new(1.0..=4.0, 2).decode(vec![false, true]), 3.);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ToFracLE<T> {
     to_int: ToIntLE<T>,
     start: T,
     a: Option<T>,
+    bits_len: usize,
 }
 
 impl<T> ToFracLE<T> {
@@ -47,6 +46,7 @@ impl<T> ToFracLE<T> {
             },
             start,
             to_int,
+            bits_len,
         }
     }
 
@@ -61,6 +61,99 @@ impl<T> ToFracLE<T> {
     }
 }
 
+impl ToFracLE<f64> {
+    /// Return bits decoding to the least number
+    /// not less than `x`,
+    /// or to the nearest bound
+    /// if `x` is outside range.
+    pub fn encode(&self, x: f64) -> Vec<bool> {
+        int_to_bits_le(self.encode_int(x), self.bits_len)
+    }
+
+    fn encode_int(&self, x: f64) -> u64 {
+        match self.a {
+            Some(a) => {
+                let max = u64::MAX >> (64 - self.bits_len);
+                let i = ((x - self.start) / a).ceil().clamp(0.0, max as f64) as u64;
+                // Division may round up
+                // past the least number.
+                if i > 0 && a * (i - 1) as f64 + self.start >= x {
+                    i - 1
+                } else {
+                    i
+                }
+            }
+            None => 0,
+        }
+    }
+}
+
+/// Reduce innermost axis
+/// of Gray-coded bits
+/// to numbers within range.
+/// Leftmost is least significant.
+///
+/// Unlike `ToFracLE`,
+/// adjacent numbers differ by only one bit.
+///
+/// # Examples
+///
+/// ```ignore
+/// // It returns lower bound for empty arrays:
+/// assert_eq!(GrayToFracLE::new(1.0..=2.0, 0).decode(vec![]), 1.);
+///
+/// // It returns lower bound when all bits are false:
+/// assert_eq!(GrayToFracLE::new(0.0..=1.0, 1).decode(vec![false]), 0.);
+/// assert_eq!(GrayToFracLE::new(1.0..=2.0, 2).decode(vec![false, false]), 1.);
+///
+/// // It returns upper bound when only the most significant bit is true:
+/// assert_eq!(GrayToFracLE::new(0.0..=1.0, 1).decode(vec![true]), 1.);
+/// assert_eq!(GrayToFracLE::new(1.0..=2.0, 2).decode(vec![false, true]), 2.);
+///
+/// // It returns a number between lower and upper bound otherwise:
+/// assert_eq!(GrayToFracLE::new(1.0..=4.0, 2).decode(vec![true, false]), 2.);
+/// assert_eq!(GrayToFracLE::new(1.0..=4.0, 2).decode(vec![true, true]), 3.);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrayToFracLE<T> {
+    to_frac: ToFracLE<T>,
+    to_int: GrayToIntLE<T>,
+}
+
+impl<T> GrayToFracLE<T> {
+    pub fn new(range: RangeInclusive<T>, bits_len: usize) -> Self
+    where
+        T: Copy + One + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+    {
+        Self {
+            to_frac: ToFracLE::new(range, bits_len),
+            to_int: GrayToIntLE::new(),
+        }
+    }
+
+    pub fn decode(&self, bits: impl IntoIterator<Item = bool>) -> T
+    where
+        T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>,
+    {
+        match self.to_frac.a {
+            Some(a) => a * self.to_int.decode(bits) + self.to_frac.start,
+            None => self.to_frac.start,
+        }
+    }
+}
+
+impl GrayToFracLE<f64> {
+    /// Return Gray-coded bits
+    /// decoding to the least number
+    /// not less than `x`,
+    /// or to the nearest bound
+    /// if `x` is outside range.
+    pub fn encode(&self, x: f64) -> Vec<bool> {
+        let i = self.to_frac.encode_int(x);
+        int_to_bits_le(i ^ (i >> 1), self.to_frac.bits_len)
+    }
+}
+
 /// Reduce to base 10 integer representations of bits.
 /// Leftmost is least significant.
 ///
@@ -68,19 +161,19 @@ impl<T> ToFracLE<T> {
 ///
 /// ```ignore
 /// // It returns 0 when empty:
-/// assert_eq!(ToIntLe::new().decode(vec![]), 0_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![]), 0_u8);
 ///
 /// // It returns the base 10 integer represented by binary bits:
-/// assert_eq!(ToIntLe::new().decode(vec![false]), 0_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![false, false]), 0_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![false, false, false]), 0_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![true]), 1_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![true, true]), 3_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![true, true, true]), 7_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![false]), 0_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![false, false]), 0_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![false, false, false]), 0_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![true]), 1_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![true, true]), 3_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![true, true, true]), 7_u8);
 ///
 /// // It treats leftmost as least significant:
-/// assert_eq!(ToIntLe::new().decode(vec![false, true]), 2_u8);
-/// assert_eq!(ToIntLe::new().decode(vec![false, false, true]), 4_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![false, true]), 2_u8);
+/// assert_eq!(ToIntLE::<u8>::new().decode(vec![false, false, true]), 4_u8);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ToIntLE<T> {
@@ -108,3 +201,166 @@ impl<T> ToIntLE<T> {
             .0
     }
 }
+
+/// Reduce to base 10 integer representations of Gray-coded bits.
+/// Leftmost is least significant.
+/// At most 64 bits are supported.
+///
+/// # Examples
+///
+/// ```ignore
+/// // It returns 0 when empty:
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![]), 0_u8);
+///
+/// // It returns the base 10 integer represented by Gray-coded bits:
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![false, false]), 0_u8);
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![true, false]), 1_u8);
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![true, true]), 2_u8);
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![false, true]), 3_u8);
+/// assert_eq!(GrayToIntLE::<u8>::new().decode(vec![true, true, true]), 5_u8);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrayToIntLE<T> {
+    to_int: ToIntLE<T>,
+}
+
+impl<T> GrayToIntLE<T> {
+    pub fn new() -> Self
+    where
+        T: One + Add<Output = T>,
+    {
+        Self {
+            to_int: ToIntLE::new(),
+        }
+    }
+
+    pub fn decode(&self, bits: impl IntoIterator<Item = bool>) -> T
+    where
+        T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>,
+    {
+        let (gray, len) = bits.into_iter().fold((0_u64, 0), |(acc, len), b| {
+            (acc | (u64::from(b) << len), len + 1)
+        });
+        // Each binary bit is the XOR
+        // of its Gray bit
+        // and all more significant Gray bits.
+        let binary = (0..u64::BITS.ilog2()).fold(gray, |x, i| x ^ (x >> (1 << i)));
+        self.to_int.decode((0..len).map(|i| (binary >> i) & 1 == 1))
+    }
+}
+
+/// Return the `len` least significant bits of `x`,
+/// least significant first.
+fn int_to_bits_le(x: u64, len: usize) -> Vec<bool> {
+    (0..len).map(|i| (x >> i) & 1 == 1).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_frac_le_returns_bounds_and_values_between() {
+        assert_eq!(ToFracLE::new(1.0..=2.0, 0).decode(vec![]), 1.);
+        assert_eq!(ToFracLE::new(0.0..=1.0, 1).decode(vec![false]), 0.);
+        assert_eq!(ToFracLE::new(1.0..=2.0, 2).decode(vec![false, false]), 1.);
+        assert_eq!(ToFracLE::new(0.0..=1.0, 1).decode(vec![true]), 1.);
+        assert_eq!(ToFracLE::new(1.0..=2.0, 2).decode(vec![true, true]), 2.);
+        assert_eq!(ToFracLE::new(1.0..=4.0, 2).decode(vec![true, false]), 2.);
+        assert_eq!(ToFracLE::new(1.0..=4.0, 2).decode(vec![false, true]), 3.);
+    }
+
+    #[test]
+    fn gray_to_frac_le_returns_bounds_and_values_between() {
+        assert_eq!(GrayToFracLE::new(1.0..=2.0, 0).decode(vec![]), 1.);
+        assert_eq!(GrayToFracLE::new(0.0..=1.0, 1).decode(vec![false]), 0.);
+        assert_eq!(
+            GrayToFracLE::new(1.0..=2.0, 2).decode(vec![false, false]),
+            1.
+        );
+        assert_eq!(GrayToFracLE::new(0.0..=1.0, 1).decode(vec![true]), 1.);
+        assert_eq!(
+            GrayToFracLE::new(1.0..=2.0, 2).decode(vec![false, true]),
+            2.
+        );
+        assert_eq!(
+            GrayToFracLE::new(1.0..=4.0, 2).decode(vec![true, false]),
+            2.
+        );
+        assert_eq!(GrayToFracLE::new(1.0..=4.0, 2).decode(vec![true, true]), 3.);
+    }
+
+    #[test]
+    fn to_int_le_returns_integer_with_leftmost_least_significant() {
+        let decoder = ToIntLE::<u8>::new();
+        assert_eq!(decoder.decode(vec![]), 0);
+        assert_eq!(decoder.decode(vec![false]), 0);
+        assert_eq!(decoder.decode(vec![false, false, false]), 0);
+        assert_eq!(decoder.decode(vec![true]), 1);
+        assert_eq!(decoder.decode(vec![true, true]), 3);
+        assert_eq!(decoder.decode(vec![true, true, true]), 7);
+        assert_eq!(decoder.decode(vec![false, true]), 2);
+        assert_eq!(decoder.decode(vec![false, false, true]), 4);
+    }
+
+    #[test]
+    fn gray_to_int_le_returns_integer_of_gray_code() {
+        let decoder = GrayToIntLE::<u8>::new();
+        assert_eq!(decoder.decode(vec![]), 0);
+        assert_eq!(decoder.decode(vec![false, false]), 0);
+        assert_eq!(decoder.decode(vec![true, false]), 1);
+        assert_eq!(decoder.decode(vec![true, true]), 2);
+        assert_eq!(decoder.decode(vec![false, true]), 3);
+        assert_eq!(decoder.decode(vec![true, true, true]), 5);
+    }
+
+    #[test]
+    fn gray_to_int_le_changes_one_bit_between_adjacent_values() {
+        let decoder = GrayToIntLE::<u16>::new();
+        let bits = |i: u16| (0..8).map(move |j| (i >> j) & 1 == 1);
+        let mut values = (0..256_u16)
+            .map(|i| (decoder.decode(bits(i)), i))
+            .collect::<Vec<_>>();
+        values.sort();
+        for ((x, i), (y, j)) in values.iter().zip(values.iter().skip(1)) {
+            assert_eq!(x + 1, *y);
+            assert_eq!((i ^ j).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn to_frac_le_encode_inverts_decode() {
+        for decoder in [
+            ToFracLE::new(0.0..=1.0, 1),
+            ToFracLE::new(1.0..=4.0, 2),
+            ToFracLE::new(3.0..=1000.0, 7),
+        ] {
+            for i in 0..(1 << decoder.bits_len) {
+                let bits = int_to_bits_le(i, decoder.bits_len);
+                assert_eq!(decoder.encode(decoder.decode(bits.clone())), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn gray_to_frac_le_encode_inverts_decode() {
+        for decoder in [
+            GrayToFracLE::new(0.0..=1.0, 1),
+            GrayToFracLE::new(1.0..=4.0, 2),
+            GrayToFracLE::new(3.0..=1000.0, 7),
+        ] {
+            for i in 0..(1 << decoder.to_frac.bits_len) {
+                let bits = int_to_bits_le(i, decoder.to_frac.bits_len);
+                assert_eq!(decoder.encode(decoder.decode(bits.clone())), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn to_frac_le_encode_returns_bounds_outside_range() {
+        let decoder = ToFracLE::new(1.0..=4.0, 2);
+        assert_eq!(decoder.encode(-10.0), vec![false, false]);
+        assert_eq!(decoder.encode(10.0), vec![true, true]);
+        assert_eq!(ToFracLE::new(1.0..=2.0, 0).encode(10.0), vec![]);
+    }
+}
@@ -0,0 +1,239 @@
+use std::num::NonZeroUsize;
+
+use ndarray::prelude::*;
+
+use crate::rect::{Rect, Size};
+
+use super::{bits_for, decode_index, free_area, trim_to_max_sizes, Decode};
+
+/// Decode bits into rectangles
+/// spanning cells of a grid
+/// over the largest area of the container
+/// free of reserved areas.
+///
+/// Bits encode,
+/// for each rectangle,
+/// its starting column and row
+/// and how many columns and rows it spans.
+/// Far fewer bits are needed
+/// than for pixel positions and sizes,
+/// and edges of different rectangles
+/// naturally align.
+/// Rectangles larger than their maximum size
+/// are trimmed evenly from both sides.
+/// Gaps and overlap
+/// are left to objectives.
+/// Minimum sizes are not kept by decoding,
+/// so layout generators make them a constraint.
+#[derive(Clone, Debug)]
+pub struct GridSpanDecoder {
+    max_sizes: Vec<Size>,
+    area: Rect,
+    columns: usize,
+    rows: usize,
+    bits_per_column: usize,
+    bits_per_row: usize,
+}
+
+impl GridSpanDecoder {
+    /// Return a decoder for `max_sizes.len()` rectangles,
+    /// where the `i`th rectangle
+    /// is no larger than `max_sizes[i]`.
+    /// `grid` is the number of columns and rows,
+    /// at most one for each pixel of the grid's area.
+    pub fn new(max_sizes: Vec<Size>, grid: Size, reserved: &[Rect], container: Size) -> Self {
+        let area = free_area(reserved, container);
+        let columns = grid.width.min(area.width()).get();
+        let rows = grid.height.min(area.height()).get();
+        Self {
+            max_sizes,
+            area,
+            columns,
+            rows,
+            bits_per_column: bits_for(columns),
+            bits_per_row: bits_for(rows),
+        }
+    }
+
+    fn decode(&self, bits: ArrayView1<bool>) -> Vec<Rect> {
+        let mut bits = bits.into_iter().copied();
+        let mut rects = (0..self.max_sizes.len())
+            .map(|_| {
+                let column = decode_index(self.columns, self.bits_per_column, &mut bits);
+                let row = decode_index(self.rows, self.bits_per_row, &mut bits);
+                let column_span =
+                    1 + decode_index(self.columns - column, self.bits_per_column, &mut bits);
+                let row_span = 1 + decode_index(self.rows - row, self.bits_per_row, &mut bits);
+                let left = edge(column, self.columns, self.area.width());
+                let top = edge(row, self.rows, self.area.height());
+                Rect::new(
+                    self.area.x() + left,
+                    self.area.y() + top,
+                    // Cells are at least one pixel,
+                    // so spans of at least one cell
+                    // are non-empty.
+                    unsafe {
+                        NonZeroUsize::new_unchecked(
+                            edge(column + column_span, self.columns, self.area.width()) - left,
+                        )
+                    },
+                    unsafe {
+                        NonZeroUsize::new_unchecked(
+                            edge(row + row_span, self.rows, self.area.height()) - top,
+                        )
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        trim_to_max_sizes(&self.max_sizes, &mut rects);
+        rects
+    }
+}
+
+impl Decode for GridSpanDecoder {
+    fn bits(&self) -> usize {
+        self.max_sizes.len() * 2 * (self.bits_per_column + self.bits_per_row)
+    }
+
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect> {
+        Array2::from_shape_vec(
+            (bits.nrows(), self.max_sizes.len()),
+            bits.rows()
+                .into_iter()
+                .flat_map(|bits| self.decode(bits))
+                .collect(),
+        )
+        .unwrap()
+    }
+}
+
+/// Return the pixel position
+/// of the `i`th of `cells + 1` grid lines
+/// along `len` pixels.
+fn edge(i: usize, cells: usize, len: NonZeroUsize) -> usize {
+    i * len.get() / cells
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::{prop::collection::vec, *};
+    use test_strategy::proptest;
+
+    use crate::{
+        encoding::free_area,
+        testing::{ContainedRects, ContainedRectsParams},
+    };
+
+    use super::*;
+
+    #[proptest]
+    fn grid_span_decoder_aligns_rects_to_grid(
+        #[strategy(arbitrary_grid_span_decoder_args())] args: (Size, Size, usize, Vec<bool>),
+    ) {
+        let (container, grid, count, bits) = args;
+        let columns = grid.width.min(container.width).get();
+        let rows = grid.height.min(container.height).get();
+        let xs = (0..=columns)
+            .map(|i| edge(i, columns, container.width))
+            .collect::<Vec<_>>();
+        let ys = (0..=rows)
+            .map(|i| edge(i, rows, container.height))
+            .collect::<Vec<_>>();
+        let rects = GridSpanDecoder::new(vec![container; count], grid, &[], container)
+            .decode1(Array::from_vec(bits).view())
+            .into_raw_vec();
+        prop_assert_eq!(rects.len(), count);
+        for rect in rects {
+            prop_assert!(xs.contains(&rect.left()));
+            prop_assert!(xs.contains(&rect.right()));
+            prop_assert!(ys.contains(&rect.top()));
+            prop_assert!(ys.contains(&rect.bottom()));
+        }
+    }
+
+    fn arbitrary_grid_span_decoder_args() -> impl Strategy<Value = (Size, Size, usize, Vec<bool>)> {
+        (
+            any::<Size>(),
+            (1_usize..=24, 1_usize..=24).prop_map(|(x, y)| Size::new_checked(x, y)),
+            1_usize..=16,
+        )
+            .prop_flat_map(|(container, grid, count)| {
+                (
+                    Just(container),
+                    Just(grid),
+                    Just(count),
+                    vec(
+                        any::<bool>(),
+                        GridSpanDecoder::new(vec![container; count], grid, &[], container).bits(),
+                    ),
+                )
+            })
+    }
+
+    #[proptest]
+    fn grid_span_decoder_respects_max_sizes_and_reserved_areas(
+        #[strategy(arbitrary_constrained_grid_span_decoder_args())] args: (
+            ContainedRects,
+            Vec<Size>,
+            Vec<bool>,
+        ),
+    ) {
+        let (
+            ContainedRects {
+                container,
+                rects: reserved,
+            },
+            max_sizes,
+            bits,
+        ) = args;
+        let area = free_area(&reserved, container);
+        prop_assume!(reserved.iter().all(|x| area.overlap(x).is_none()));
+        let rects = GridSpanDecoder::new(
+            max_sizes.clone(),
+            Size::new_checked(12, 8),
+            &reserved,
+            container,
+        )
+        .decode1(Array::from_vec(bits).view())
+        .into_raw_vec();
+        prop_assert_eq!(rects.len(), max_sizes.len());
+        for (rect, max_size) in rects.iter().zip(&max_sizes) {
+            prop_assert!(rect.right() <= container.width.get());
+            prop_assert!(rect.bottom() <= container.height.get());
+            prop_assert!(rect.width() <= max_size.width);
+            prop_assert!(rect.height() <= max_size.height);
+            for reserved in &reserved {
+                prop_assert_eq!(rect.overlap(reserved), None);
+            }
+        }
+    }
+
+    fn arbitrary_constrained_grid_span_decoder_args(
+    ) -> impl Strategy<Value = (ContainedRects, Vec<Size>, Vec<bool>)> {
+        (
+            any_with::<ContainedRects>(ContainedRectsParams::from_len_range(0..=2)),
+            1_usize..=8,
+        )
+            .prop_flat_map(|(x, count)| {
+                let container = x.container;
+                (
+                    Just(x),
+                    vec(
+                        (1..=container.width.get(), 1..=container.height.get())
+                            .prop_map(|(width, height)| Size::new_checked(width, height)),
+                        count,
+                    ),
+                )
+            })
+            .prop_flat_map(|(x, max_sizes)| {
+                let bits = GridSpanDecoder::new(
+                    max_sizes.clone(),
+                    Size::new_checked(12, 8),
+                    &x.rects,
+                    x.container,
+                )
+                .bits();
+                (Just(x), Just(max_sizes), vec(any::<bool>(), bits))
+            })
+    }
+}
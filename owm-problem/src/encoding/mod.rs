@@ -0,0 +1,651 @@
+mod grid_span;
+mod slicing;
+mod template;
+
+use std::{
+    fmt,
+    num::NonZeroUsize,
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use ndarray::prelude::*;
+
+use crate::{
+    binary::{GrayToFracLE, ToFracLE, ToIntLE},
+    post_processing::{div_ceil, remove_gaps, trim_outside},
+    rect::{Rect, Size},
+    region::Length,
+};
+
+pub use self::{
+    grid_span::GridSpanDecoder,
+    slicing::SlicingDecoder,
+    template::{Template, TemplateDecoder, TemplateFromStrError},
+};
+
+/// A way to decode bits into rectangles.
+pub trait Decode {
+    /// Return the number of bits decoded.
+    fn bits(&self) -> usize;
+
+    /// Decode each row of bits
+    /// into a row of rectangles.
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect>;
+
+    fn decode1(&self, bits: ArrayView1<bool>) -> Array1<Rect> {
+        Array::from_vec(
+            self.decode2(bits.into_shape((1, bits.len())).unwrap())
+                .into_raw_vec(),
+        )
+    }
+}
+
+impl<D> Decode for Box<D>
+where
+    D: Decode + ?Sized,
+{
+    fn bits(&self) -> usize {
+        (**self).bits()
+    }
+
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect> {
+        (**self).decode2(bits)
+    }
+}
+
+/// How layouts are encoded as bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Independent position and size for each window,
+    /// see `Decoder`.
+    Free,
+    /// Recursive splits of the container,
+    /// see `SlicingDecoder`.
+    Slicing,
+    /// Spans of cells on a grid,
+    /// see `GridSpanDecoder`.
+    GridSpan,
+}
+
+/// Error returned when failing to parse 'Encoding'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'free', 'slicing', or 'grid-span'")]
+pub struct EncodingFromStrError(String);
+
+impl FromStr for Encoding {
+    type Err = EncodingFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "free" => Ok(Self::Free),
+            "slicing" => Ok(Self::Slicing),
+            "grid-span" => Ok(Self::GridSpan),
+            _ => Err(EncodingFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Free => write!(f, "free"),
+            Self::Slicing => write!(f, "slicing"),
+            Self::GridSpan => write!(f, "grid-span"),
+        }
+    }
+}
+
+/// How numbers are represented as bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitCode {
+    /// Plain binary,
+    /// where flipping a high bit
+    /// makes a large jump.
+    Binary,
+    /// Gray code,
+    /// where adjacent numbers
+    /// differ by only one bit.
+    Gray,
+}
+
+/// Error returned when failing to parse 'BitCode'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'binary' or 'gray'")]
+pub struct BitCodeFromStrError(String);
+
+impl FromStr for BitCode {
+    type Err = BitCodeFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "binary" => Ok(Self::Binary),
+            "gray" => Ok(Self::Gray),
+            _ => Err(BitCodeFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for BitCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Binary => write!(f, "binary"),
+            Self::Gray => write!(f, "gray"),
+        }
+    }
+}
+
+/// Decode bits into an independent position and size
+/// for each rectangle,
+/// then repair gaps.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    max_sizes: Vec<Size>,
+    reserved: Vec<Rect>,
+    container: Size,
+    rect_decoders: Vec<RectDecoder>,
+}
+
+#[derive(Clone, Debug)]
+struct RectDecoder {
+    x_decoder: ToFrac,
+    y_decoder: ToFrac,
+    width_decoder: ToFrac,
+    height_decoder: ToFrac,
+    x_bits_range: Range<usize>,
+    y_bits_range: Range<usize>,
+    width_bits_range: Range<usize>,
+    height_bits_range: Range<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum ToFrac {
+    Binary(ToFracLE<f64>),
+    Gray(GrayToFracLE<f64>),
+}
+
+impl Decoder {
+    /// Return a decoder for `min_sizes.len()` rectangles,
+    /// where the `i`th rectangle
+    /// is bounded by `min_sizes[i]` and `max_sizes[i]`.
+    /// Positions and sizes are encoded
+    /// in steps of about `resolution`,
+    /// resolved against each axis of `container`,
+    /// and represented by `code`.
+    /// Rectangles grow up to `reserved` areas,
+    /// but not into them.
+    pub fn new(
+        min_sizes: Vec<Size>,
+        max_sizes: Vec<Size>,
+        resolution: Length,
+        code: BitCode,
+        reserved: Vec<Rect>,
+        container: Size,
+    ) -> Self {
+        debug_assert_eq!(min_sizes.len(), max_sizes.len());
+
+        let step = Size::new_checked(
+            resolution.resolve(container.width.get()).max(1),
+            resolution.resolve(container.height.get()).max(1),
+        );
+        let mut start = 0;
+        let rect_decoders = min_sizes
+            .into_iter()
+            .zip(max_sizes.iter())
+            .map(|(min_size, max_size)| {
+                let decoder = RectDecoder::new(start, min_size, *max_size, step, code, container);
+                start = decoder.height_bits_range.end;
+                decoder
+            })
+            .collect();
+        Self {
+            max_sizes,
+            reserved,
+            container,
+            rect_decoders,
+        }
+    }
+
+    /// Return bits decoding to `rects`,
+    /// or near them
+    /// if they cannot be decoded exactly.
+    ///
+    /// Bits are exact for rectangles
+    /// decoded before repairing gaps.
+    pub fn encode(&self, rects: &[Rect]) -> Array1<bool> {
+        debug_assert_eq!(rects.len(), self.rect_decoders.len());
+        let mut bits = Array1::from_elem(self.bits(), false);
+        for (decoder, rect) in self.rect_decoders.iter().zip(rects) {
+            decoder.encode(*rect, bits.view_mut());
+        }
+        bits
+    }
+}
+
+impl Decode for Decoder {
+    fn bits(&self) -> usize {
+        self.rect_decoders
+            .last()
+            .map_or(0, |decoder| decoder.height_bits_range.end)
+    }
+
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect> {
+        let mut rects =
+            Array2::from_shape_fn((bits.nrows(), self.rect_decoders.len()), |(i, j)| {
+                self.rect_decoders[j].decode(bits.row(i))
+            });
+        for mut rects in rects.axis_iter_mut(Axis(0)) {
+            trim_outside(self.container, rects.as_slice_mut().unwrap());
+            remove_gaps(
+                &self.max_sizes,
+                &self.reserved,
+                self.container,
+                rects.as_slice_mut().unwrap(),
+            );
+        }
+        rects
+    }
+}
+
+impl RectDecoder {
+    fn new(
+        start: usize,
+        min_size: Size,
+        max_size: Size,
+        step: Size,
+        code: BitCode,
+        container: Size,
+    ) -> Self {
+        debug_assert!(min_size.width <= max_size.width);
+        debug_assert!(min_size.height <= max_size.height);
+        debug_assert!(max_size.width <= container.width);
+        debug_assert!(max_size.height <= container.height);
+
+        let x_max = container.width.get().saturating_sub(min_size.width.get());
+        let y_max = container.height.get().saturating_sub(min_size.height.get());
+        let width_range = min_size.width.get()..=max_size.width.get();
+        let height_range = min_size.height.get()..=max_size.height.get();
+        let bits_per_x = reduced_bits_for(x_max, step.width.get());
+        let bits_per_y = reduced_bits_for(y_max, step.height.get());
+        let bits_per_width =
+            reduced_bits_for(width_range.end() - width_range.start(), step.width.get());
+        let bits_per_height =
+            reduced_bits_for(height_range.end() - height_range.start(), step.height.get());
+        let x_start = start;
+        let y_start = x_start + bits_per_x;
+        let width_start = y_start + bits_per_y;
+        let height_start = width_start + bits_per_width;
+        Self {
+            x_decoder: ToFrac::new(code, 0.0..=(x_max as f64), bits_per_x),
+            y_decoder: ToFrac::new(code, 0.0..=(y_max as f64), bits_per_y),
+            width_decoder: ToFrac::new(
+                code,
+                (*width_range.start() as f64)..=(*width_range.end() as f64),
+                bits_per_width,
+            ),
+            height_decoder: ToFrac::new(
+                code,
+                (*height_range.start() as f64)..=(*height_range.end() as f64),
+                bits_per_height,
+            ),
+            x_bits_range: x_start..y_start,
+            y_bits_range: y_start..width_start,
+            width_bits_range: width_start..height_start,
+            height_bits_range: height_start..(height_start + bits_per_height),
+        }
+    }
+
+    fn decode(&self, bits: ArrayView1<bool>) -> Rect {
+        let width = self.width_decoder.decode(
+            bits.slice(s![self.width_bits_range.clone()])
+                .into_iter()
+                .copied(),
+        ) as usize;
+        let height = self.height_decoder.decode(
+            bits.slice(s![self.height_bits_range.clone()])
+                .into_iter()
+                .copied(),
+        ) as usize;
+        Rect::new(
+            self.x_decoder.decode(
+                bits.slice(s![self.x_bits_range.clone()])
+                    .into_iter()
+                    .copied(),
+            ) as usize,
+            self.y_decoder.decode(
+                bits.slice(s![self.y_bits_range.clone()])
+                    .into_iter()
+                    .copied(),
+            ) as usize,
+            // The decoder should ensure these invariants.
+            unsafe { NonZeroUsize::new_unchecked(width) },
+            unsafe { NonZeroUsize::new_unchecked(height) },
+        )
+    }
+
+    fn encode(&self, rect: Rect, mut bits: ArrayViewMut1<bool>) {
+        for (decoder, range, x) in [
+            (&self.x_decoder, &self.x_bits_range, rect.x()),
+            (&self.y_decoder, &self.y_bits_range, rect.y()),
+            (
+                &self.width_decoder,
+                &self.width_bits_range,
+                rect.width().get(),
+            ),
+            (
+                &self.height_decoder,
+                &self.height_bits_range,
+                rect.height().get(),
+            ),
+        ] {
+            for (bit, x) in bits
+                .slice_mut(s![range.clone()])
+                .iter_mut()
+                .zip(decoder.encode(x as f64))
+            {
+                *bit = x;
+            }
+        }
+    }
+}
+
+/// Return probabilities of each bit being `true`,
+/// biased toward `bits`
+/// by `bias` in `0..=0.5`,
+/// for starting an optimizer
+/// near a known layout,
+/// like the previous layout
+/// and one more window.
+pub fn seed_probabilities(bits: ArrayView1<bool>, bias: f64) -> Array1<f64> {
+    let bias = bias.clamp(0.0, 0.5);
+    bits.map(|bit| if *bit { 0.5 + bias } else { 0.5 - bias })
+}
+
+impl ToFrac {
+    fn new(code: BitCode, range: RangeInclusive<f64>, bits_len: usize) -> Self {
+        match code {
+            BitCode::Binary => Self::Binary(ToFracLE::new(range, bits_len)),
+            BitCode::Gray => Self::Gray(GrayToFracLE::new(range, bits_len)),
+        }
+    }
+
+    fn decode(&self, bits: impl IntoIterator<Item = bool>) -> f64 {
+        match self {
+            Self::Binary(x) => x.decode(bits),
+            Self::Gray(x) => x.decode(bits),
+        }
+    }
+
+    fn encode(&self, x: f64) -> Vec<bool> {
+        match self {
+            Self::Binary(decoder) => decoder.encode(x),
+            Self::Gray(decoder) => decoder.encode(x),
+        }
+    }
+}
+
+/// Return the largest region of `container`
+/// not overlapping any `obstacles`,
+/// or `container`
+/// if obstacles leave no room.
+fn free_area(obstacles: &[Rect], container: Size) -> Rect {
+    let edges = |len: usize, ranges: &mut dyn Iterator<Item = (usize, usize)>| {
+        let mut edges = ranges
+            .flat_map(|(start, end)| [start.min(len), end.min(len)])
+            .chain([0, len])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    };
+    let xs = edges(
+        container.width.get(),
+        &mut obstacles.iter().map(|x| (x.left(), x.right())),
+    );
+    let ys = edges(
+        container.height.get(),
+        &mut obstacles.iter().map(|x| (x.top(), x.bottom())),
+    );
+    xs.iter()
+        .tuple_combinations()
+        .cartesian_product(ys.iter().tuple_combinations().collect::<Vec<_>>())
+        .map(|((left, right), (top, bottom))| {
+            Rect::new_checked(*left, *top, right - left, bottom - top)
+        })
+        .filter(|area| obstacles.iter().all(|x| area.overlap(x).is_none()))
+        .max_by_key(|area| area.area())
+        .unwrap_or(Rect::new(0, 0, container.width, container.height))
+}
+
+/// Shrink each rectangle evenly from both sides
+/// until it fits within its maximum size.
+fn trim_to_max_sizes(max_sizes: &[Size], rects: &mut [Rect]) {
+    debug_assert_eq!(max_sizes.len(), rects.len());
+    for (rect, max_size) in rects.iter_mut().zip(max_sizes) {
+        let excess_width = rect.width().get().saturating_sub(max_size.width.get());
+        let excess_height = rect.height().get().saturating_sub(max_size.height.get());
+        rect.shrink_left(excess_width / 2);
+        rect.shrink_right(excess_width - excess_width / 2);
+        rect.shrink_top(excess_height / 2);
+        rect.shrink_bottom(excess_height - excess_height / 2);
+    }
+}
+
+/// Return bits needed
+/// to represent `0..=x`
+/// in about `step` increments.
+fn reduced_bits_for(x: usize, step: usize) -> usize {
+    bits_for(div_ceil(x, step))
+}
+
+/// Return an index less than `len`
+/// decoded from the next `bits_len` bits.
+fn decode_index(len: usize, bits_len: usize, bits: &mut impl Iterator<Item = bool>) -> usize {
+    ToIntLE::<usize>::new().decode(bits.take(bits_len)) * len / (1 << bits_len)
+}
+
+fn bits_for(x: usize) -> usize {
+    if x == 0 {
+        0
+    } else if x == 1 {
+        1
+    } else {
+        (x - 1).ilog2() as usize + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::{prop::collection::vec, *};
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[test]
+    fn encoding_round_trips_through_strings() {
+        for encoding in [Encoding::Free, Encoding::Slicing, Encoding::GridSpan] {
+            assert_eq!(encoding.to_string().parse::<Encoding>(), Ok(encoding))
+        }
+    }
+
+    #[test]
+    fn bit_code_round_trips_through_strings() {
+        for code in [BitCode::Binary, BitCode::Gray] {
+            assert_eq!(code.to_string().parse::<BitCode>(), Ok(code))
+        }
+    }
+
+    #[proptest]
+    fn decoder_respects_min_and_max_sizes(#[strategy(arbitrary_decoder_args())] args: DecoderArgs) {
+        let decoder = Decoder::new(
+            args.min_sizes.clone(),
+            args.max_sizes.clone(),
+            args.resolution,
+            args.code,
+            Vec::new(),
+            args.container,
+        );
+        prop_assert_eq!(decoder.bits(), args.bits.len());
+        for ((rect, min_size), max_size) in decoder
+            .decode1(Array::from_vec(args.bits).view())
+            .into_iter()
+            .zip(args.min_sizes)
+            .zip(args.max_sizes)
+        {
+            prop_assert!(rect.width() >= min_size.width);
+            prop_assert!(rect.height() >= min_size.height);
+            prop_assert!(rect.width() <= max_size.width);
+            prop_assert!(rect.height() <= max_size.height);
+            prop_assert!(rect.right() <= args.container.width.get());
+            prop_assert!(rect.bottom() <= args.container.height.get());
+        }
+    }
+
+    #[proptest]
+    fn decoder_encode_inverts_decode_before_repair(
+        #[strategy(arbitrary_decoder_args())] args: DecoderArgs,
+    ) {
+        let decoder = Decoder::new(
+            args.min_sizes,
+            args.max_sizes,
+            args.resolution,
+            args.code,
+            Vec::new(),
+            args.container,
+        );
+        let bits = Array::from_vec(args.bits);
+        let rects = decoder
+            .rect_decoders
+            .iter()
+            .map(|x| x.decode(bits.view()))
+            .collect::<Vec<_>>();
+        let encoded = decoder.encode(&rects);
+        prop_assert_eq!(
+            decoder
+                .rect_decoders
+                .iter()
+                .map(|x| x.decode(encoded.view()))
+                .collect::<Vec<_>>(),
+            rects
+        );
+    }
+
+    #[test]
+    fn seed_probabilities_are_biased_toward_bits() {
+        assert_eq!(
+            seed_probabilities(array![true, false].view(), 0.25),
+            array![0.75, 0.25]
+        );
+        assert_eq!(
+            seed_probabilities(array![true, false].view(), 1.0),
+            array![1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn free_area_avoids_obstacles() {
+        let container = Size::new_checked(100, 50);
+        assert_eq!(free_area(&[], container), Rect::new_checked(0, 0, 100, 50));
+        assert_eq!(
+            free_area(&[Rect::new_checked(0, 0, 30, 50)], container),
+            Rect::new_checked(30, 0, 70, 50)
+        );
+        assert_eq!(
+            free_area(
+                &[
+                    Rect::new_checked(0, 0, 100, 10),
+                    Rect::new_checked(30, 20, 10, 10)
+                ],
+                container
+            ),
+            Rect::new_checked(40, 10, 60, 40)
+        );
+        assert_eq!(
+            free_area(&[Rect::new_checked(0, 0, 100, 50)], container),
+            Rect::new_checked(0, 0, 100, 50)
+        );
+    }
+
+    #[test]
+    fn decoder_uses_more_bits_for_finer_resolution() {
+        let container = Size::new_checked(1920, 1080);
+        let min_sizes = vec![Size::new_checked(1, 1); 2];
+        let max_sizes = vec![container; 2];
+        let bits = |resolution| {
+            Decoder::new(
+                min_sizes.clone(),
+                max_sizes.clone(),
+                resolution,
+                BitCode::Binary,
+                Vec::new(),
+                container,
+            )
+            .bits()
+        };
+        assert!(bits(Length::Pixels(16)) > bits(Length::Pixels(128)));
+        assert!(bits(Length::Percent(1.0)) > bits(Length::Percent(10.0)));
+        assert_eq!(bits(Length::Pixels(0)), bits(Length::Pixels(1)));
+    }
+
+    #[derive(Clone, Debug)]
+    struct DecoderArgs {
+        container: Size,
+        min_sizes: Vec<Size>,
+        max_sizes: Vec<Size>,
+        resolution: Length,
+        code: BitCode,
+        bits: Vec<bool>,
+    }
+
+    fn arbitrary_decoder_args() -> impl Strategy<Value = DecoderArgs> {
+        (any::<Size>(), 1_usize..=4)
+            .prop_flat_map(|(container, count)| {
+                (
+                    Just(container),
+                    prop_oneof![
+                        (0_usize..=512).prop_map(Length::Pixels),
+                        (0.0..=100.0).prop_map(Length::Percent),
+                    ],
+                    prop_oneof![Just(BitCode::Binary), Just(BitCode::Gray)],
+                    vec(
+                        (
+                            1..=container.width.get(),
+                            1..=container.height.get(),
+                            1..=container.width.get(),
+                            1..=container.height.get(),
+                        )
+                            .prop_map(
+                                |(width1, height1, width2, height2)| {
+                                    (
+                                        Size::new_checked(width1.min(width2), height1.min(height2)),
+                                        Size::new_checked(width1.max(width2), height1.max(height2)),
+                                    )
+                                },
+                            ),
+                        count,
+                    ),
+                )
+            })
+            .prop_flat_map(|(container, resolution, code, sizes)| {
+                let (min_sizes, max_sizes): (Vec<_>, Vec<_>) = sizes.into_iter().unzip();
+                let bits = Decoder::new(
+                    min_sizes.clone(),
+                    max_sizes.clone(),
+                    resolution,
+                    code,
+                    Vec::new(),
+                    container,
+                )
+                .bits();
+                vec(any::<bool>(), bits).prop_map(move |bits| DecoderArgs {
+                    container,
+                    min_sizes: min_sizes.clone(),
+                    max_sizes: max_sizes.clone(),
+                    resolution,
+                    code,
+                    bits,
+                })
+            })
+    }
+}
@@ -0,0 +1,422 @@
+use std::num::NonZeroUsize;
+
+use ndarray::prelude::*;
+
+use crate::{
+    binary::ToFracLE,
+    rect::{Rect, Size},
+    region::Length,
+};
+
+use super::{bits_for, decode_index, free_area, reduced_bits_for, trim_to_max_sizes, Decode};
+
+/// Decode bits into rectangles
+/// tiling the largest area of the container
+/// free of reserved areas,
+/// by recursively splitting it in two.
+///
+/// Rectangles larger than their maximum size
+/// are trimmed,
+/// then rectangles grow to fill gaps,
+/// so decoded layouts never overlap
+/// each other or reserved areas,
+/// unless the area has fewer pixels than rectangles.
+/// Minimum sizes are not kept by decoding,
+/// so layout generators make them a constraint.
+///
+/// Bits encode,
+/// for each split,
+/// which region to split,
+/// whether to split it vertically or horizontally,
+/// and where to split it,
+/// then which region each rectangle is assigned.
+#[derive(Clone, Debug)]
+pub struct SlicingDecoder {
+    count: usize,
+    max_sizes: Vec<Size>,
+    reserved: Vec<Rect>,
+    container: Size,
+    area: Rect,
+    ratio_decoder: ToFracLE<f64>,
+    bits_per_ratio: usize,
+}
+
+impl SlicingDecoder {
+    /// Return a decoder for `max_sizes.len()` rectangles,
+    /// where the `i`th rectangle
+    /// is no larger than `max_sizes[i]`.
+    /// Splits are encoded
+    /// in steps of about `resolution`,
+    /// resolved against the longer axis of `container`.
+    pub fn new(
+        max_sizes: Vec<Size>,
+        resolution: Length,
+        reserved: Vec<Rect>,
+        container: Size,
+    ) -> Self {
+        let len = container.width.get().max(container.height.get());
+        let bits_per_ratio = reduced_bits_for(len, resolution.resolve(len).max(1)).max(1);
+        Self {
+            count: max_sizes.len(),
+            area: free_area(&reserved, container),
+            max_sizes,
+            reserved,
+            container,
+            ratio_decoder: ToFracLE::new(0.0..=1.0, bits_per_ratio),
+            bits_per_ratio,
+        }
+    }
+
+    fn decode(&self, bits: ArrayView1<bool>) -> Vec<Rect> {
+        let mut bits = bits.into_iter().copied();
+        let mut regions = Vec::with_capacity(self.count);
+        if self.count > 0 {
+            regions.push(self.area);
+        }
+        for len in 1..self.count {
+            let i = decode_index(len, bits_for(len), &mut bits);
+            let vertical = bits.next().unwrap();
+            let ratio = self
+                .ratio_decoder
+                .decode(bits.by_ref().take(self.bits_per_ratio));
+            // A region too small to split
+            // would leave two rectangles
+            // in the same place.
+            // The largest region can always be split
+            // when the container has room for every rectangle.
+            let i = if is_splittable(regions[i]) {
+                i
+            } else {
+                (0..len).max_by_key(|i| regions[*i].area()).unwrap()
+            };
+            match split(regions[i], vertical, ratio) {
+                Some((region, new_region)) => {
+                    regions[i] = region;
+                    regions.push(new_region);
+                }
+                // Every region is a single pixel,
+                // so remaining rectangles
+                // share the last region.
+                None => regions.push(regions[len - 1]),
+            }
+        }
+        let mut rects = (0..self.count)
+            .map(|i| {
+                regions.remove(decode_index(
+                    self.count - i,
+                    bits_for(self.count - i),
+                    &mut bits,
+                ))
+            })
+            .collect::<Vec<_>>();
+        trim_to_max_sizes(&self.max_sizes, &mut rects);
+        grow_in_turn(&self.max_sizes, &self.reserved, self.container, &mut rects);
+        rects
+    }
+}
+
+impl Decode for SlicingDecoder {
+    fn bits(&self) -> usize {
+        (1..self.count)
+            .map(|len| bits_for(len) + 1 + self.bits_per_ratio)
+            .chain((1..=self.count).map(bits_for))
+            .sum()
+    }
+
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect> {
+        Array2::from_shape_vec(
+            (bits.nrows(), self.count),
+            bits.rows()
+                .into_iter()
+                .flat_map(|bits| self.decode(bits))
+                .collect(),
+        )
+        .unwrap()
+    }
+}
+
+/// Grow each rectangle in turn,
+/// as far as its maximum size allows,
+/// up to other rectangles and `obstacles`,
+/// but not into them.
+///
+/// Unlike growing every rectangle at once,
+/// growing in turn never makes rectangles overlap.
+fn grow_in_turn(max_sizes: &[Size], obstacles: &[Rect], container: Size, rects: &mut [Rect]) {
+    debug_assert_eq!(max_sizes.len(), rects.len());
+    for (i, max_size) in max_sizes.iter().enumerate() {
+        let others = rects[..i]
+            .iter()
+            .chain(&rects[i + 1..])
+            .chain(obstacles)
+            .copied()
+            .collect::<Vec<_>>();
+        let rect = &mut rects[i];
+
+        let y_range = rect.y_range_exclusive();
+        let beside = || {
+            others
+                .iter()
+                .filter(move |other| y_range.intersects(other.y_range_exclusive()))
+        };
+        let max_free = max_size.width.get().saturating_sub(rect.width().get());
+        let left = beside()
+            .filter(|other| other.right() <= rect.left())
+            .map(|other| other.right())
+            .max()
+            .unwrap_or(0)
+            .max(rect.left().saturating_sub(max_free));
+        rect.expand_left(rect.left() - left);
+        let max_free = max_size.width.get().saturating_sub(rect.width().get());
+        let right = beside()
+            .filter(|other| rect.right() <= other.left())
+            .map(|other| other.left())
+            .min()
+            .unwrap_or(container.width.get())
+            .min(rect.right() + max_free);
+        rect.expand_right(right - rect.right());
+
+        let x_range = rect.x_range_exclusive();
+        let above_or_below = || {
+            others
+                .iter()
+                .filter(move |other| x_range.intersects(other.x_range_exclusive()))
+        };
+        let max_free = max_size.height.get().saturating_sub(rect.height().get());
+        let top = above_or_below()
+            .filter(|other| other.bottom() <= rect.top())
+            .map(|other| other.bottom())
+            .max()
+            .unwrap_or(0)
+            .max(rect.top().saturating_sub(max_free));
+        rect.expand_top(rect.top() - top);
+        let max_free = max_size.height.get().saturating_sub(rect.height().get());
+        let bottom = above_or_below()
+            .filter(|other| rect.bottom() <= other.top())
+            .map(|other| other.top())
+            .min()
+            .unwrap_or(container.height.get())
+            .min(rect.bottom() + max_free);
+        rect.expand_bottom(bottom - rect.bottom());
+    }
+}
+
+fn is_splittable(rect: Rect) -> bool {
+    rect.width().get() > 1 || rect.height().get() > 1
+}
+
+/// Split `rect` in two
+/// at `ratio` of its width,
+/// if `vertical`,
+/// or its height.
+/// The other direction is used
+/// if `rect` is too small to split as requested.
+/// Return `None`
+/// if `rect` is a single pixel.
+fn split(rect: Rect, vertical: bool, ratio: f64) -> Option<(Rect, Rect)> {
+    let vertical = if vertical {
+        rect.width().get() > 1 || rect.height().get() == 1
+    } else {
+        rect.height().get() == 1
+    };
+    let len = if vertical {
+        rect.width().get()
+    } else {
+        rect.height().get()
+    };
+    if len < 2 {
+        return None;
+    }
+    let at = 1 + (ratio.clamp(0.0, 1.0) * (len - 2) as f64).round() as usize;
+    // `1 <= at < len`,
+    // so both sides are non-empty.
+    let first = unsafe { NonZeroUsize::new_unchecked(at) };
+    let second = unsafe { NonZeroUsize::new_unchecked(len - at) };
+    Some(if vertical {
+        (
+            Rect::new(rect.x(), rect.y(), first, rect.height()),
+            Rect::new(rect.x() + at, rect.y(), second, rect.height()),
+        )
+    } else {
+        (
+            Rect::new(rect.x(), rect.y(), rect.width(), first),
+            Rect::new(rect.x(), rect.y() + at, rect.width(), second),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::{prop::collection::vec, *};
+    use test_strategy::proptest;
+
+    use crate::{
+        encoding::free_area,
+        rect::obscured_area,
+        testing::{ContainedRects, ContainedRectsParams},
+    };
+
+    use super::*;
+
+    #[proptest]
+    fn slicing_decoder_tiles_container(
+        #[strategy(arbitrary_slicing_decoder_args())] args: (Size, usize, Vec<bool>),
+    ) {
+        let (container, count, bits) = args;
+        prop_assume!(container.area().get() >= 2 * count);
+        let rects = SlicingDecoder::new(
+            vec![container; count],
+            Length::Pixels(128),
+            Vec::new(),
+            container,
+        )
+        .decode1(Array::from_vec(bits).view())
+        .into_raw_vec();
+        prop_assert_eq!(rects.len(), count);
+        for rect in &rects {
+            prop_assert!(rect.right() <= container.width.get());
+            prop_assert!(rect.bottom() <= container.height.get());
+        }
+        prop_assert_eq!(obscured_area(&rects), 0);
+        prop_assert_eq!(
+            rects.iter().map(|x| x.area().get()).sum::<usize>(),
+            container.area().get()
+        );
+    }
+
+    #[proptest]
+    fn split_returns_two_halves_covering_rect(
+        #[strategy(arbitrary_splittable_rect())] rect: Rect,
+        vertical: bool,
+        #[strategy(-1.0..=2.0)] ratio: f64,
+    ) {
+        let (first, second) = split(rect, vertical, ratio).unwrap();
+        prop_assert_eq!(first.overlap(&second), None);
+        prop_assert_eq!(first.area().get() + second.area().get(), rect.area().get());
+        for half in [first, second] {
+            prop_assert_eq!(half.overlap(&rect), Some(half));
+        }
+    }
+
+    fn arbitrary_splittable_rect() -> impl Strategy<Value = Rect> {
+        (
+            0_usize..=5120,
+            0_usize..=2160,
+            1_usize..=5120,
+            1_usize..=2160,
+        )
+            .prop_map(|(x, y, width, height)| Rect::new_checked(x, y, width, height))
+            .prop_filter("rect should be splittable", |rect| is_splittable(*rect))
+    }
+
+    #[test]
+    fn split_returns_none_for_single_pixel() {
+        for vertical in [false, true] {
+            assert_eq!(split(Rect::new_checked(3, 4, 1, 1), vertical, 0.5), None)
+        }
+    }
+
+    #[test]
+    fn slicing_decoder_returns_count_rects_in_too_small_container() {
+        let container = Size::new_checked(2, 1);
+        let decoder = SlicingDecoder::new(
+            vec![container; 4],
+            Length::Pixels(128),
+            Vec::new(),
+            container,
+        );
+        let rects = decoder
+            .decode1(Array::from_elem(decoder.bits(), false).view())
+            .into_raw_vec();
+        assert_eq!(rects.len(), 4);
+        for rect in rects {
+            assert!(rect.right() <= container.width.get());
+        }
+    }
+
+    fn arbitrary_slicing_decoder_args() -> impl Strategy<Value = (Size, usize, Vec<bool>)> {
+        (any::<Size>(), 1_usize..=16).prop_flat_map(|(container, count)| {
+            (
+                Just(container),
+                Just(count),
+                vec(
+                    any::<bool>(),
+                    SlicingDecoder::new(
+                        vec![container; count],
+                        Length::Pixels(128),
+                        Vec::new(),
+                        container,
+                    )
+                    .bits(),
+                ),
+            )
+        })
+    }
+
+    #[proptest]
+    fn slicing_decoder_respects_max_sizes_and_reserved_areas(
+        #[strategy(arbitrary_constrained_slicing_decoder_args())] args: (
+            ContainedRects,
+            Vec<Size>,
+            Vec<bool>,
+        ),
+    ) {
+        let (
+            ContainedRects {
+                container,
+                rects: reserved,
+            },
+            max_sizes,
+            bits,
+        ) = args;
+        let area = free_area(&reserved, container);
+        prop_assume!(area.area().get() >= 2 * max_sizes.len());
+        prop_assume!(reserved.iter().all(|x| area.overlap(x).is_none()));
+        let rects = SlicingDecoder::new(
+            max_sizes.clone(),
+            Length::Pixels(128),
+            reserved.clone(),
+            container,
+        )
+        .decode1(Array::from_vec(bits).view())
+        .into_raw_vec();
+        prop_assert_eq!(rects.len(), max_sizes.len());
+        for (rect, max_size) in rects.iter().zip(&max_sizes) {
+            prop_assert!(rect.width() <= max_size.width);
+            prop_assert!(rect.height() <= max_size.height);
+            for reserved in &reserved {
+                prop_assert_eq!(rect.overlap(reserved), None);
+            }
+        }
+        prop_assert_eq!(obscured_area(&rects), 0);
+    }
+
+    fn arbitrary_constrained_slicing_decoder_args(
+    ) -> impl Strategy<Value = (ContainedRects, Vec<Size>, Vec<bool>)> {
+        (
+            any_with::<ContainedRects>(ContainedRectsParams::from_len_range(0..=2)),
+            1_usize..=8,
+        )
+            .prop_flat_map(|(x, count)| {
+                let container = x.container;
+                (
+                    Just(x),
+                    vec(
+                        (1..=container.width.get(), 1..=container.height.get())
+                            .prop_map(|(width, height)| Size::new_checked(width, height)),
+                        count,
+                    ),
+                )
+            })
+            .prop_flat_map(|(x, max_sizes)| {
+                let bits = SlicingDecoder::new(
+                    max_sizes.clone(),
+                    Length::Pixels(128),
+                    x.rects.clone(),
+                    x.container,
+                )
+                .bits();
+                (Just(x), Just(max_sizes), vec(any::<bool>(), bits))
+            })
+    }
+}
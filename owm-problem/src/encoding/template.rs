@@ -0,0 +1,216 @@
+use std::{fmt, num::NonZeroUsize, str::FromStr};
+
+use ndarray::{concatenate, prelude::*};
+
+use crate::{
+    rect::{Rect, Size},
+    region::{Length, Region},
+};
+
+use super::Decode;
+
+/// A region of the container
+/// a window is fixed to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Template {
+    /// Full height at the left of the container,
+    /// with the given width.
+    Left(Length),
+    /// An arbitrary region.
+    Region(Region),
+}
+
+/// Error returned when failing to parse 'Template'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not of the form 'left:WIDTH' or 'X,Y,WIDTH,HEIGHT'")]
+pub struct TemplateFromStrError(String);
+
+impl Template {
+    /// Return this region in pixels,
+    /// moved and shrunk as needed
+    /// to cover at least one pixel
+    /// of `container`.
+    pub fn resolve(&self, container: Size) -> Rect {
+        match self {
+            Self::Left(width) => Rect::new(
+                0,
+                0,
+                clamp_len(width.resolve(container.width.get()), container.width.get()),
+                container.height,
+            ),
+            Self::Region(region) => {
+                let x = region
+                    .x
+                    .resolve(container.width.get())
+                    .min(container.width.get() - 1);
+                let y = region
+                    .y
+                    .resolve(container.height.get())
+                    .min(container.height.get() - 1);
+                Rect::new(
+                    x,
+                    y,
+                    clamp_len(
+                        region.width.resolve(container.width.get()),
+                        container.width.get() - x,
+                    ),
+                    clamp_len(
+                        region.height.resolve(container.height.get()),
+                        container.height.get() - y,
+                    ),
+                )
+            }
+        }
+    }
+}
+
+fn clamp_len(len: usize, max: usize) -> NonZeroUsize {
+    len.clamp(1, max).try_into().unwrap()
+}
+
+impl FromStr for Template {
+    type Err = TemplateFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("left:") {
+            Some(width) => width
+                .parse()
+                .map(Self::Left)
+                .map_err(|_| TemplateFromStrError(s.to_owned())),
+            None => s
+                .parse()
+                .map(Self::Region)
+                .map_err(|_| TemplateFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left(width) => write!(f, "left:{width}"),
+            Self::Region(region) => write!(f, "{region}"),
+        }
+    }
+}
+
+/// Fix the first windows to regions,
+/// then decode the rest
+/// like another decoder.
+///
+/// Only the remaining windows are encoded,
+/// but decoded layouts include every window,
+/// so objectives and post-processing
+/// see the full layout.
+#[derive(Clone, Debug)]
+pub struct TemplateDecoder<D> {
+    fixed: Vec<Rect>,
+    decoder: D,
+}
+
+impl<D> TemplateDecoder<D> {
+    /// `decoder` decodes windows
+    /// after those in `fixed`.
+    pub fn new(fixed: Vec<Rect>, decoder: D) -> Self {
+        Self { fixed, decoder }
+    }
+}
+
+impl<D> Decode for TemplateDecoder<D>
+where
+    D: Decode,
+{
+    fn bits(&self) -> usize {
+        self.decoder.bits()
+    }
+
+    fn decode2(&self, bits: ArrayView2<bool>) -> Array2<Rect> {
+        let fixed = ArrayView1::from(&self.fixed);
+        let fixed = fixed.broadcast((bits.nrows(), self.fixed.len())).unwrap();
+        concatenate(Axis(1), &[fixed, self.decoder.decode2(bits).view()]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::{prop::collection::vec, *};
+    use test_strategy::proptest;
+
+    use crate::{
+        encoding::GridSpanDecoder,
+        testing::{ContainedRects, ContainedRectsParams},
+    };
+
+    use super::*;
+
+    #[test]
+    fn template_round_trips_through_strings() {
+        for template in [
+            Template::Left(Length::Pixels(800)),
+            Template::Left(Length::Percent(50.0)),
+            Template::Region(Region {
+                x: Length::Pixels(0),
+                y: Length::Percent(10.0),
+                width: Length::Percent(50.0),
+                height: Length::Pixels(600),
+            }),
+        ] {
+            assert_eq!(template.to_string().parse::<Template>(), Ok(template))
+        }
+        assert!("right:50%".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn left_template_covers_full_height() {
+        let container = Size::new_checked(1000, 800);
+        assert_eq!(
+            Template::Left(Length::Percent(60.0)).resolve(container),
+            Rect::new_checked(0, 0, 600, 800)
+        );
+    }
+
+    #[proptest]
+    fn template_decoder_keeps_fixed_windows_first(
+        #[strategy(arbitrary_template_decoder_args())] args: (ContainedRects, usize, Vec<bool>),
+    ) {
+        let (
+            ContainedRects {
+                container,
+                rects: fixed,
+            },
+            count,
+            bits,
+        ) = args;
+        let rects = TemplateDecoder::new(
+            fixed.clone(),
+            GridSpanDecoder::new(
+                vec![container; count],
+                Size::new_checked(12, 8),
+                &[],
+                container,
+            ),
+        )
+        .decode1(Array::from_vec(bits).view())
+        .into_raw_vec();
+        prop_assert_eq!(rects.len(), fixed.len() + count);
+        prop_assert_eq!(&rects[..fixed.len()], &fixed[..]);
+    }
+
+    fn arbitrary_template_decoder_args() -> impl Strategy<Value = (ContainedRects, usize, Vec<bool>)>
+    {
+        (
+            any_with::<ContainedRects>(ContainedRectsParams::from_len_range(0..=2)),
+            0_usize..=8,
+        )
+            .prop_flat_map(|(x, count)| {
+                let bits = GridSpanDecoder::new(
+                    vec![x.container; count],
+                    Size::new_checked(12, 8),
+                    &[],
+                    x.container,
+                )
+                .bits();
+                (Just(x), Just(count), vec(any::<bool>(), bits))
+            })
+    }
+}
@@ -1,6 +1,7 @@
 mod binary;
 mod derive;
 mod rect;
+mod region;
 
 pub mod encoding;
 pub mod objective;
@@ -10,6 +11,11 @@ pub mod post_processing;
 pub mod testing;
 
 pub use crate::{
-    objective::{AreaRatio, AspectRatio, Weight, Weights},
+    objective::{
+        Adjacency, AreaRatio, AspectRatio, Constraint, GridCount, IndexGroup, MainAnchor,
+        ReadingDirection, ReadingMajor, ReadingOrder, SymmetryAxes, VisibleFraction, Weight,
+        Weights,
+    },
     rect::{Pos, Rect, Size},
+    region::{Length, LengthFromStrError, Region, RegionFromStrError},
 };
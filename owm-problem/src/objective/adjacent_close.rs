@@ -1,21 +1,65 @@
+use std::{fmt, str::FromStr};
+
 use itertools::Itertools;
 
 use crate::{Pos, Rect, Size};
 
 pub struct PlaceAdjacentClose {
+    adjacency: Adjacency,
     worst_case: f64,
 }
 
+/// How closeness of consecutive windows is measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Distance between nearest corners.
+    #[default]
+    Corners,
+    /// Length of shared border.
+    Contact,
+}
+
+/// Error returned when failing to parse 'Adjacency'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'corners' or 'contact'")]
+pub struct AdjacencyFromStrError(String);
+
+impl FromStr for Adjacency {
+    type Err = AdjacencyFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "corners" => Ok(Self::Corners),
+            "contact" => Ok(Self::Contact),
+            _ => Err(AdjacencyFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Adjacency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Corners => write!(f, "corners"),
+            Self::Contact => write!(f, "contact"),
+        }
+    }
+}
+
 impl PlaceAdjacentClose {
-    pub fn new(container: Size, count: usize) -> Self {
-        Self {
+    pub fn new(adjacency: Adjacency, container: Size, count: usize) -> Self {
+        let worst_case_per_pair = match adjacency {
             // This assumes rectangles cannot exceed container bounds.
             // `container.width.get()` is not `- 1`
             // because we only compare *some* corners.
-            worst_case: (count.saturating_sub(1)
-                * (Pos::new(1, 1))
-                    .dist(Pos::new(container.width.get(), container.height.get() - 1)))
+            Adjacency::Corners => (Pos::new(1, 1))
+                .dist(Pos::new(container.width.get(), container.height.get() - 1))
                 as f64,
+            // Rectangles sharing no border.
+            Adjacency::Contact => 1.0,
+        };
+        Self {
+            adjacency,
+            worst_case: count.saturating_sub(1) as f64 * worst_case_per_pair,
         }
     }
 
@@ -26,27 +70,61 @@ impl PlaceAdjacentClose {
             rects
                 .iter()
                 .tuple_windows()
-                .map(|(rect, other)| {
-                    [
-                        rect.top_left().dist(other.top_right()),
-                        rect.top_left().dist(other.bottom_left()),
-                        rect.top_right().dist(other.top_left()),
-                        rect.top_right().dist(other.bottom_right()),
-                        rect.bottom_left().dist(other.top_left()),
-                        rect.bottom_left().dist(other.bottom_right()),
-                        rect.bottom_right().dist(other.top_right()),
-                        rect.bottom_right().dist(other.bottom_left()),
-                    ]
-                    .into_iter()
-                    .min()
-                    .unwrap()
+                .map(|(rect, other)| match self.adjacency {
+                    Adjacency::Corners => corner_distance(rect, other) as f64,
+                    Adjacency::Contact => 1.0 - contact(rect, other),
                 })
-                .sum::<usize>() as f64
+                .sum::<f64>()
                 / self.worst_case
         }
     }
 }
 
+/// Return the fraction of the shorter facing edge
+/// `rect` and `other` share
+/// on any side,
+/// in range 0..=1.
+fn contact(rect: &Rect, other: &Rect) -> f64 {
+    let beside = if rect.right() == other.left() || rect.left() == other.right() {
+        rect.y_range_exclusive()
+            .intersection_len(other.y_range_exclusive()) as f64
+            / rect.height().min(other.height()).get() as f64
+    } else {
+        0.0
+    };
+    let above_or_below = if rect.bottom() == other.top() || rect.top() == other.bottom() {
+        rect.x_range_exclusive()
+            .intersection_len(other.x_range_exclusive()) as f64
+            / rect.width().min(other.width()).get() as f64
+    } else {
+        0.0
+    };
+    beside.max(above_or_below)
+}
+
+/// Return the distance between the nearest corners
+/// of `rect` and `other`
+/// that can touch without overlap.
+///
+/// The same corners are compared
+/// when both rectangles are mirrored or transposed,
+/// so no reading order is favored.
+fn corner_distance(rect: &Rect, other: &Rect) -> usize {
+    [
+        rect.top_left().dist(other.top_right()),
+        rect.top_left().dist(other.bottom_left()),
+        rect.top_right().dist(other.top_left()),
+        rect.top_right().dist(other.bottom_right()),
+        rect.bottom_left().dist(other.top_left()),
+        rect.bottom_left().dist(other.bottom_right()),
+        rect.bottom_right().dist(other.top_right()),
+        rect.bottom_right().dist(other.bottom_left()),
+    ]
+    .into_iter()
+    .min()
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -57,9 +135,13 @@ mod tests {
     use super::*;
 
     #[proptest]
-    fn place_adjacent_close_returns_values_in_range_0_1(x: ContainedRects) {
-        prop_assert!((0.0..=1.0)
-            .contains(&PlaceAdjacentClose::new(x.container, x.rects.len()).evaluate(&x.rects)))
+    fn place_adjacent_close_returns_values_in_range_0_1(
+        #[strategy(arbitrary_adjacency())] adjacency: Adjacency,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0).contains(
+            &PlaceAdjacentClose::new(adjacency, x.container, x.rects.len()).evaluate(&x.rects)
+        ))
     }
 
     #[test]
@@ -72,7 +154,7 @@ mod tests {
             Rect::new_checked(0, 0, 1, 1),
         ];
         assert_eq!(
-            PlaceAdjacentClose::new(container, rects.len()).evaluate(&rects),
+            PlaceAdjacentClose::new(Adjacency::default(), container, rects.len()).evaluate(&rects),
             1.0
         )
     }
@@ -86,8 +168,100 @@ mod tests {
             Rect::new_checked(5, 5, 5, 5),
         ];
         assert_eq!(
-            PlaceAdjacentClose::new(container, rects.len()).evaluate(&rects),
+            PlaceAdjacentClose::new(Adjacency::default(), container, rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[proptest]
+    fn place_adjacent_close_values_mirrored_layouts_equally(
+        #[strategy(arbitrary_adjacency())] adjacency: Adjacency,
+        x: ContainedRects,
+    ) {
+        let objective = PlaceAdjacentClose::new(adjacency, x.container, x.rects.len());
+        let value = objective.evaluate(&x.rects);
+        for mirrored in [
+            x.rects
+                .iter()
+                .map(|rect| rect.mirror_horizontally(x.container))
+                .collect::<Vec<_>>(),
+            x.rects
+                .iter()
+                .map(|rect| rect.mirror_vertically(x.container))
+                .collect(),
+        ] {
+            prop_assert_eq!(objective.evaluate(&mirrored), value);
+        }
+    }
+
+    #[test]
+    fn place_adjacent_close_by_contact_returns_1_for_worst_case() {
+        // Touching corners share no border.
+        let container = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+            Rect::new_checked(0, 0, 5, 5),
+        ];
+        assert_eq!(
+            PlaceAdjacentClose::new(Adjacency::Contact, container, rects.len()).evaluate(&rects),
+            1.0
+        )
+    }
+
+    #[test]
+    fn place_adjacent_close_by_contact_returns_0_for_best_case() {
+        let container = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(0, 0, 5, 10),
+            Rect::new_checked(5, 0, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+        ];
+        assert_eq!(
+            PlaceAdjacentClose::new(Adjacency::Contact, container, rects.len()).evaluate(&rects),
             0.0
         )
     }
+
+    #[test]
+    fn place_adjacent_close_by_contact_scales_with_shared_border() {
+        let container = Size::new_checked(10, 10);
+        let rects = [Rect::new_checked(0, 0, 5, 4), Rect::new_checked(5, 2, 5, 4)];
+        assert_eq!(
+            PlaceAdjacentClose::new(Adjacency::Contact, container, rects.len()).evaluate(&rects),
+            0.5
+        )
+    }
+
+    #[test]
+    fn place_adjacent_close_by_contact_counts_left_and_above_neighbors() {
+        let container = Size::new_checked(10, 10);
+        for rects in [
+            [
+                Rect::new_checked(5, 0, 5, 10),
+                Rect::new_checked(0, 0, 5, 10),
+            ],
+            [
+                Rect::new_checked(0, 5, 10, 5),
+                Rect::new_checked(0, 0, 10, 5),
+            ],
+        ] {
+            assert_eq!(
+                PlaceAdjacentClose::new(Adjacency::Contact, container, rects.len())
+                    .evaluate(&rects),
+                0.0
+            )
+        }
+    }
+
+    #[test]
+    fn adjacency_round_trips_through_strings() {
+        for adjacency in [Adjacency::Corners, Adjacency::Contact] {
+            assert_eq!(adjacency.to_string().parse::<Adjacency>(), Ok(adjacency))
+        }
+    }
+
+    fn arbitrary_adjacency() -> impl Strategy<Value = Adjacency> {
+        prop_oneof![Just(Adjacency::Corners), Just(Adjacency::Contact)]
+    }
 }
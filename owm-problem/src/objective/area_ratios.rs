@@ -18,6 +18,7 @@ use crate::{
 
 pub struct MaintainAreaRatios {
     ratios: Vec<AreaRatio>,
+    main: usize,
     worst_case: f64,
 }
 
@@ -43,7 +44,11 @@ impl Mul<f64> for AreaRatio {
 }
 
 impl MaintainAreaRatios {
-    pub fn new(ratios: Vec<AreaRatio>, max_size: Size, count: usize) -> Self {
+    /// `main` is the index of the window
+    /// given the main window's area,
+    /// usually `0`.
+    /// Other windows keep their order.
+    pub fn new(ratios: Vec<AreaRatio>, main: usize, max_size: Size, count: usize) -> Self {
         let worst_case = if !ratios.is_empty() && count > 1 {
             Self::_evaluate(
                 ratios
@@ -58,7 +63,11 @@ impl MaintainAreaRatios {
         } else {
             0.0
         };
-        Self { ratios, worst_case }
+        Self {
+            ratios,
+            main,
+            worst_case,
+        }
     }
 
     pub fn evaluate(&self, rects: &[Rect]) -> f64 {
@@ -70,7 +79,17 @@ impl MaintainAreaRatios {
                     .iter()
                     .chain(repeat(self.ratios.last().unwrap()))
                     .copied(),
-                rects.iter().map(|x| x.area()),
+                rects
+                    .get(self.main)
+                    .into_iter()
+                    .chain(
+                        rects
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| *i != self.main)
+                            .map(|(_, rect)| rect),
+                    )
+                    .map(|x| x.area()),
             ) / self.worst_case
         }
     }
@@ -114,6 +133,7 @@ mod tests {
                     .into_iter()
                     .map(|x| AreaRatio::new(x).unwrap())
                     .collect(),
+                0,
                 x.container,
                 x.rects.len()
             )
@@ -138,7 +158,8 @@ mod tests {
             Rect::new_checked(0, 0, 10, 10),
         ];
         assert_eq!(
-            MaintainAreaRatios::new(vec![AreaRatio(2.0)], max_size, rects.len()).evaluate(&rects),
+            MaintainAreaRatios::new(vec![AreaRatio(2.0)], 0, max_size, rects.len())
+                .evaluate(&rects),
             1.0
         )
     }
@@ -152,7 +173,23 @@ mod tests {
             Rect::new_checked(0, 0, 5, 5),
         ];
         assert_eq!(
-            MaintainAreaRatios::new(vec![AreaRatio(2.0)], max_size, rects.len()).evaluate(&rects),
+            MaintainAreaRatios::new(vec![AreaRatio(2.0)], 0, max_size, rects.len())
+                .evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn maintain_area_ratios_returns_0_for_emphasized_best_case() {
+        let max_size = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(0, 0, 10, 5),
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(0, 0, 10, 10),
+        ];
+        assert_eq!(
+            MaintainAreaRatios::new(vec![AreaRatio(2.0)], 2, max_size, rects.len())
+                .evaluate(&rects),
             0.0
         )
     }
@@ -1,25 +1,177 @@
+use std::{fmt, str::FromStr};
+
 use crate::{Pos, Rect, Size};
 
+use crate::{Length, LengthFromStrError};
+
+/// Pull the main window toward an anchor,
+/// the container center by default.
 pub struct CenterMain {
-    center: Pos,
+    anchor: MainAnchor,
+    main: usize,
+    target: Pos,
     worst_case: f64,
 }
 
+/// Where the main window should be placed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MainAnchor {
+    /// Center of window at center of container.
+    Center,
+    /// Left edge of window at left edge of container.
+    Left,
+    /// Right edge of window at right edge of container.
+    Right,
+    /// Top edge of window at top edge of container.
+    Top,
+    /// Bottom edge of window at bottom edge of container.
+    Bottom,
+    /// Center of window at a point in container.
+    Point(Length, Length),
+}
+
+/// Error returned when failing to parse 'MainAnchor'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+pub enum MainAnchorFromStrError {
+    /// Value is not a known anchor or point.
+    #[error("'{0}' is not one of 'center', 'left', 'right', 'top', 'bottom', or 'X,Y'")]
+    Unknown(String),
+    /// A coordinate is not a valid length.
+    #[error("{0}")]
+    Length(LengthFromStrError),
+}
+
+impl FromStr for MainAnchor {
+    type Err = MainAnchorFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "center" => Ok(Self::Center),
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "top" => Ok(Self::Top),
+            "bottom" => Ok(Self::Bottom),
+            _ => match s.split_once(',') {
+                Some((x, y)) => Ok(Self::Point(
+                    x.parse().map_err(Self::Err::Length)?,
+                    y.parse().map_err(Self::Err::Length)?,
+                )),
+                None => Err(Self::Err::Unknown(s.to_owned())),
+            },
+        }
+    }
+}
+
+impl fmt::Display for MainAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Center => write!(f, "center"),
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+            Self::Top => write!(f, "top"),
+            Self::Bottom => write!(f, "bottom"),
+            Self::Point(x, y) => write!(f, "{x},{y}"),
+        }
+    }
+}
+
+// Points are in half pixels,
+// so centers of odd-sized windows
+// are exact
+// and mirrored layouts are valued equally.
+// The center anchor instead rounds centers down
+// and is normalized by distance to container corners,
+// valuing layouts exactly like it always has.
+impl MainAnchor {
+    /// Return the point in `container`
+    /// the main window should be placed at,
+    /// in half pixels.
+    fn target(self, container: Size) -> Pos {
+        let width = container.width.get();
+        let height = container.height.get();
+        match self {
+            Self::Center => Pos::new(2 * (width / 2), 2 * (height / 2)),
+            Self::Left => Pos::new(0, height),
+            Self::Right => Pos::new(2 * width, height),
+            Self::Top => Pos::new(width, 0),
+            Self::Bottom => Pos::new(width, 2 * height),
+            Self::Point(x, y) => Pos::new(
+                2 * x.resolve(width).min(width - 1),
+                2 * y.resolve(height).min(height - 1),
+            ),
+        }
+    }
+
+    /// Return the point of `rect`
+    /// compared to the target,
+    /// in half pixels.
+    fn reference(self, rect: &Rect) -> Pos {
+        let center_x = 2 * rect.left() + rect.width().get();
+        let center_y = 2 * rect.top() + rect.height().get();
+        match self {
+            Self::Center => Pos::new(2 * rect.center_x(), 2 * rect.center_y()),
+            Self::Point(_, _) => Pos::new(center_x, center_y),
+            Self::Left => Pos::new(2 * rect.left(), center_y),
+            Self::Right => Pos::new(2 * rect.right(), center_y),
+            Self::Top => Pos::new(center_x, 2 * rect.top()),
+            Self::Bottom => Pos::new(center_x, 2 * rect.bottom()),
+        }
+    }
+
+    /// Return corners of the region
+    /// reference points can be in,
+    /// or of the container for the center anchor,
+    /// in half pixels.
+    /// This assumes rectangles cannot exceed container bounds.
+    fn reference_bounds(self, container: Size) -> [Pos; 4] {
+        let width = container.width.get();
+        let height = container.height.get();
+        let (left, right) = match self {
+            Self::Center => (0, 2 * width),
+            Self::Left => (0, 2 * width - 2),
+            Self::Right => (2, 2 * width),
+            _ => (1, 2 * width - 1),
+        };
+        let (top, bottom) = match self {
+            Self::Center => (0, 2 * height),
+            Self::Top => (0, 2 * height - 2),
+            Self::Bottom => (2, 2 * height),
+            _ => (1, 2 * height - 1),
+        };
+        [
+            Pos::new(left, top),
+            Pos::new(right, top),
+            Pos::new(left, bottom),
+            Pos::new(right, bottom),
+        ]
+    }
+}
+
 impl CenterMain {
-    pub fn new(container: Size) -> Self {
-        let center = Pos::new(container.width.get() / 2, container.height.get() / 2);
+    /// `main` is the index of the window
+    /// treated as main,
+    /// usually `0`.
+    pub fn new(anchor: MainAnchor, main: usize, container: Size) -> Self {
+        let target = anchor.target(container);
         Self {
-            center,
-            worst_case: center
-                .dist(Pos::new(0, 0))
-                .max(center.dist(container.into())) as f64,
+            anchor,
+            main,
+            target,
+            worst_case: anchor
+                .reference_bounds(container)
+                .into_iter()
+                .map(|corner| corner.dist(target))
+                .max()
+                .unwrap() as f64,
         }
     }
 
     pub fn evaluate(&self, rects: &[Rect]) -> f64 {
-        match rects.get(0) {
-            Some(rect) => rect.center().dist(self.center) as f64 / self.worst_case,
-            None => 0.0,
+        match rects.get(self.main) {
+            Some(rect) if self.worst_case > 0.0 => {
+                self.anchor.reference(rect).dist(self.target) as f64 / self.worst_case
+            }
+            _ => 0.0,
         }
     }
 }
@@ -37,8 +189,13 @@ mod tests {
     use super::*;
 
     #[proptest]
-    fn center_main_returns_values_in_range_0_1(x: ContainedRects) {
-        prop_assert!((0.0..=1.0).contains(&CenterMain::new(x.container).evaluate(&x.rects)))
+    fn center_main_returns_values_in_range_0_1(
+        #[strategy(arbitrary_main_anchor())] anchor: MainAnchor,
+        x: ContainedRects,
+    ) {
+        prop_assert!(
+            (0.0..=1.0).contains(&CenterMain::new(anchor, 0, x.container).evaluate(&x.rects))
+        )
     }
 
     #[test]
@@ -49,7 +206,27 @@ mod tests {
             Rect::new_checked(0, 5, 5, 5),
             Rect::new_checked(0, 0, 10, 10),
         ];
-        assert_eq!(CenterMain::new(container).evaluate(&rects), 1.0)
+        assert_eq!(
+            CenterMain::new(MainAnchor::Center, 0, container).evaluate(&rects),
+            1.0
+        );
+        for anchor in [MainAnchor::Right, MainAnchor::Bottom] {
+            assert_eq!(CenterMain::new(anchor, 0, container).evaluate(&rects), 1.0);
+        }
+        for (anchor, main) in [
+            (MainAnchor::Left, Rect::new_checked(9, 0, 1, 1)),
+            (MainAnchor::Top, Rect::new_checked(0, 9, 1, 1)),
+            (
+                MainAnchor::Point(Length::Pixels(0), Length::Pixels(0)),
+                Rect::new_checked(9, 9, 1, 1),
+            ),
+        ] {
+            assert_eq!(
+                CenterMain::new(anchor, 0, container).evaluate(&[main]),
+                1.0,
+                "{anchor}"
+            );
+        }
     }
 
     #[test]
@@ -60,18 +237,127 @@ mod tests {
             Rect::new_checked(0, 0, 12, 12),
             Rect::new_checked(0, 5, 5, 5),
         ];
-        assert_eq!(CenterMain::new(container).evaluate(&rects), 0.0)
+        assert_eq!(
+            CenterMain::new(MainAnchor::Center, 0, container).evaluate(&rects),
+            0.0
+        )
     }
 
-    #[proptest]
-    fn center_main_returns_0_for_full_main(x: ContainedRects) {
+    #[test]
+    fn center_main_values_centered_main_like_baseline_in_odd_sized_container() {
+        let container = Size::new_checked(11, 7);
+        // The center rounds down to `(5, 3)`,
+        // at most `6 + 4` from a container corner,
+        // and the window center rounds down to `(2, 1)`.
+        let rects = [Rect::new_checked(0, 0, 5, 3)];
         assert_eq!(
-            CenterMain::new(x.container).evaluate(
-                &once(Rect::new(0, 0, x.container.width, x.container.height))
-                    .chain(x.rects)
-                    .collect_vec()
+            CenterMain::new(MainAnchor::Center, 0, container).evaluate(&rects),
+            5.0 / 10.0
+        )
+    }
+
+    #[proptest]
+    fn center_main_values_mirrored_layouts_within_a_pixel(x: ContainedRects) {
+        let objective = CenterMain::new(MainAnchor::Center, 0, x.container);
+        let value = objective.evaluate(&x.rects);
+        for mirrored in [
+            x.rects
+                .iter()
+                .map(|rect| rect.mirror_horizontally(x.container))
+                .collect::<Vec<_>>(),
+            x.rects
+                .iter()
+                .map(|rect| rect.mirror_vertically(x.container))
+                .collect(),
+        ] {
+            // Distances are in half pixels.
+            prop_assert!(
+                (objective.evaluate(&mirrored) - value).abs() * objective.worst_case <= 2.0 + 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn center_main_returns_0_for_anchored_main() {
+        let container = Size::new_checked(12, 12);
+        for (anchor, main) in [
+            (MainAnchor::Left, Rect::new_checked(0, 0, 6, 12)),
+            (MainAnchor::Right, Rect::new_checked(6, 0, 6, 12)),
+            (MainAnchor::Top, Rect::new_checked(0, 0, 12, 6)),
+            (MainAnchor::Bottom, Rect::new_checked(0, 6, 12, 6)),
+            (
+                MainAnchor::Point(Length::Percent(25.0), Length::Percent(50.0)),
+                Rect::new_checked(0, 0, 6, 12),
             ),
+        ] {
+            assert_eq!(
+                CenterMain::new(anchor, 0, container).evaluate(&[main]),
+                0.0,
+                "{anchor}"
+            )
+        }
+    }
+
+    #[test]
+    fn center_main_returns_0_for_centered_emphasized_window() {
+        let container = Size::new_checked(12, 12);
+        let rects = [
+            Rect::new_checked(0, 0, 12, 6),
+            Rect::new_checked(3, 3, 6, 6),
+            Rect::new_checked(0, 6, 12, 6),
+        ];
+        assert_eq!(
+            CenterMain::new(MainAnchor::Center, 1, container).evaluate(&rects),
             0.0
         )
     }
+
+    #[proptest]
+    fn center_main_returns_0_for_full_main(x: ContainedRects) {
+        for anchor in [
+            MainAnchor::Center,
+            MainAnchor::Left,
+            MainAnchor::Right,
+            MainAnchor::Top,
+            MainAnchor::Bottom,
+        ] {
+            prop_assert_eq!(
+                CenterMain::new(anchor, 0, x.container).evaluate(
+                    &once(Rect::new(0, 0, x.container.width, x.container.height))
+                        .chain(x.rects.iter().copied())
+                        .collect_vec()
+                ),
+                0.0
+            )
+        }
+    }
+
+    #[test]
+    fn main_anchor_round_trips_through_strings() {
+        for anchor in [
+            MainAnchor::Center,
+            MainAnchor::Left,
+            MainAnchor::Right,
+            MainAnchor::Top,
+            MainAnchor::Bottom,
+            MainAnchor::Point(Length::Percent(33.5), Length::Pixels(720)),
+        ] {
+            assert_eq!(anchor.to_string().parse::<MainAnchor>(), Ok(anchor))
+        }
+    }
+
+    fn arbitrary_main_anchor() -> impl Strategy<Value = MainAnchor> {
+        let length = prop_oneof![
+            (0_usize..=8192).prop_map(Length::Pixels),
+            (0.0..=100.0).prop_map(Length::Percent),
+        ];
+        prop_oneof![
+            Just(MainAnchor::Center),
+            Just(MainAnchor::Left),
+            Just(MainAnchor::Right),
+            Just(MainAnchor::Top),
+            Just(MainAnchor::Bottom),
+            (length.clone(), length).prop_map(|(x, y)| MainAnchor::Point(x, y)),
+        ]
+    }
 }
@@ -0,0 +1,159 @@
+use std::{fmt, str::FromStr};
+
+/// An objective
+/// that can be weighted
+/// or made a constraint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    Gaps,
+    Overlap,
+    AreaRatios,
+    AspectRatios,
+    AdjacentClose,
+    ReadingOrder,
+    CenterMain,
+    Consistency,
+    Symmetry,
+    EdgeAlignment,
+    Reserved,
+    Grid,
+    Sliceable,
+    GroupEquality,
+    Readable,
+    Hidden,
+}
+
+/// A requirement layouts must meet.
+/// Any layout meeting all constraints
+/// is better than any layout that does not.
+/// Layouts that do not
+/// are better the less they violate constraints,
+/// regardless of objectives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// Objective must be fully satisfied.
+    Objective(Objective),
+    /// Windows must be inside usable area.
+    Containment,
+    /// Windows must be at least minimum size.
+    MinSizes,
+    /// Windows must be no larger than the window before.
+    SizeOrder,
+}
+
+/// Error returned when failing to parse 'Objective'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not an objective")]
+pub struct ObjectiveFromStrError(String);
+
+/// Error returned when failing to parse 'Constraint'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not an objective, 'containment', 'min-sizes', or 'size-order'")]
+pub struct ConstraintFromStrError(String);
+
+impl Objective {
+    pub const ALL: [Self; 16] = [
+        Self::Gaps,
+        Self::Overlap,
+        Self::AreaRatios,
+        Self::AspectRatios,
+        Self::AdjacentClose,
+        Self::ReadingOrder,
+        Self::CenterMain,
+        Self::Consistency,
+        Self::Symmetry,
+        Self::EdgeAlignment,
+        Self::Reserved,
+        Self::Grid,
+        Self::Sliceable,
+        Self::GroupEquality,
+        Self::Readable,
+        Self::Hidden,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Gaps => "gaps",
+            Self::Overlap => "overlap",
+            Self::AreaRatios => "area-ratios",
+            Self::AspectRatios => "aspect-ratios",
+            Self::AdjacentClose => "adjacent-close",
+            Self::ReadingOrder => "reading-order",
+            Self::CenterMain => "center-main",
+            Self::Consistency => "consistency",
+            Self::Symmetry => "symmetry",
+            Self::EdgeAlignment => "edge-alignment",
+            Self::Reserved => "reserved",
+            Self::Grid => "grid",
+            Self::Sliceable => "sliceable",
+            Self::GroupEquality => "group-equality",
+            Self::Readable => "readable",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+impl FromStr for Objective {
+    type Err = ObjectiveFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|objective| objective.name() == s)
+            .ok_or_else(|| ObjectiveFromStrError(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Constraint {
+    type Err = ConstraintFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "containment" => Ok(Self::Containment),
+            "min-sizes" => Ok(Self::MinSizes),
+            "size-order" => Ok(Self::SizeOrder),
+            _ => s
+                .parse()
+                .map(Self::Objective)
+                .map_err(|_| ConstraintFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Objective(x) => write!(f, "{x}"),
+            Self::Containment => write!(f, "containment"),
+            Self::MinSizes => write!(f, "min-sizes"),
+            Self::SizeOrder => write!(f, "size-order"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constraint_round_trips_through_strings() {
+        for constraint in Objective::ALL
+            .into_iter()
+            .map(Constraint::Objective)
+            .chain([
+                Constraint::Containment,
+                Constraint::MinSizes,
+                Constraint::SizeOrder,
+            ])
+        {
+            assert_eq!(constraint.to_string().parse::<Constraint>(), Ok(constraint))
+        }
+        assert!("overlap-weight".parse::<Constraint>().is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{Rect, Size};
+
+/// Keep windows inside the container.
+pub struct StayContained {
+    container: Rect,
+}
+
+impl StayContained {
+    pub fn new(container: Size) -> Self {
+        Self {
+            container: Rect::new(0, 0, container.width, container.height),
+        }
+    }
+
+    /// Return the fraction of window area
+    /// outside the container.
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        let area = rects.iter().map(|rect| rect.area().get()).sum::<usize>();
+        if area == 0 {
+            0.0
+        } else {
+            let inside = rects
+                .iter()
+                .filter_map(|rect| rect.overlap(&self.container))
+                .map(|rect| rect.area().get())
+                .sum::<usize>();
+            (area - inside) as f64 / area as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn stay_contained_returns_0_for_contained_rects(x: ContainedRects) {
+        prop_assert_eq!(StayContained::new(x.container).evaluate(&x.rects), 0.0)
+    }
+
+    #[test]
+    fn stay_contained_returns_1_for_worst_case() {
+        let container = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(10, 0, 5, 5),
+            Rect::new_checked(0, 10, 5, 5),
+        ];
+        assert_eq!(StayContained::new(container).evaluate(&rects), 1.0)
+    }
+
+    #[test]
+    fn stay_contained_returns_fraction_outside() {
+        let container = Size::new_checked(10, 10);
+        let rects = [Rect::new_checked(5, 0, 10, 10)];
+        assert_eq!(StayContained::new(container).evaluate(&rects), 0.5)
+    }
+}
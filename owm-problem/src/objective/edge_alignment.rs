@@ -0,0 +1,181 @@
+use itertools::Itertools;
+
+use crate::{Rect, Size};
+
+pub struct AlignEdges {
+    container: Size,
+    tolerance: usize,
+    best_case: usize,
+    worst_case: f64,
+}
+
+impl AlignEdges {
+    /// Edges closer than or equal to `tolerance`,
+    /// but not equal,
+    /// are near-misses,
+    /// penalized in addition to the number of distinct edges.
+    pub fn new(container: Size, tolerance: usize, count: usize) -> Self {
+        // This assumes rectangles cannot exceed container bounds.
+        // Only edges strictly inside the container are counted,
+        // so each axis has at most `length - 1` distinct edges.
+        let worst_case_for = |length: usize| {
+            let distinct = (2 * count).min(length - 1);
+            let near_misses = if tolerance > 0 {
+                distinct.saturating_sub(1)
+            } else {
+                0
+            };
+            distinct + near_misses
+        };
+        let best_case = best_case_for(container, count);
+        Self {
+            container,
+            tolerance,
+            best_case,
+            worst_case: (worst_case_for(container.width.get())
+                + worst_case_for(container.height.get()))
+            .saturating_sub(best_case) as f64,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            (self.misalignment(
+                self.container.width.get(),
+                rects.iter().flat_map(|rect| [rect.left(), rect.right()]),
+            ) + self.misalignment(
+                self.container.height.get(),
+                rects.iter().flat_map(|rect| [rect.top(), rect.bottom()]),
+            ))
+            .saturating_sub(self.best_case) as f64
+                / self.worst_case
+        }
+    }
+
+    fn misalignment(&self, length: usize, edges: impl Iterator<Item = usize>) -> usize {
+        let edges = edges
+            .filter(|x| 0 < *x && *x < length)
+            .sorted_unstable()
+            .dedup()
+            .collect_vec();
+        edges.len()
+            + edges
+                .iter()
+                .tuple_windows()
+                .filter(|(x, y)| *y - *x <= self.tolerance)
+                .count()
+    }
+}
+
+/// Return the fewest distinct edges
+/// strictly inside `container`
+/// a tiling of `count` rectangles can have.
+///
+/// A grid of `a` by `b` lines
+/// has room for `(a + 1) * (b + 1)` rectangles,
+/// and any fewer rectangles
+/// can tile it by spanning cells.
+fn best_case_for(container: Size, count: usize) -> usize {
+    let max_columns = container.width.get() - 1;
+    let max_rows = container.height.get() - 1;
+    (0..=max_columns + max_rows)
+        .find(|lines| {
+            (lines.saturating_sub(max_rows)..=max_columns.min(*lines))
+                .any(|columns| (columns + 1) * (lines - columns + 1) >= count)
+        })
+        .unwrap_or(max_columns + max_rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::repeat_n;
+
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn align_edges_returns_values_in_range_0_1(
+        #[strategy(0_usize..=64)] tolerance: usize,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0)
+            .contains(&AlignEdges::new(x.container, tolerance, x.rects.len()).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn align_edges_returns_1_for_worst_case() {
+        let container = Size::new_checked(10, 10);
+        let rects = [Rect::new_checked(1, 1, 1, 1), Rect::new_checked(3, 3, 1, 1)];
+        assert_eq!(
+            AlignEdges::new(container, 1, rects.len()).evaluate(&rects),
+            1.0
+        );
+        let rects = [Rect::new_checked(1, 1, 1, 1), Rect::new_checked(5, 5, 1, 1)];
+        assert_eq!(
+            AlignEdges::new(container, 0, rects.len()).evaluate(&rects),
+            1.0
+        );
+    }
+
+    #[proptest]
+    fn align_edges_returns_0_for_best_case(
+        #[strategy(0_usize..=64)] tolerance: usize,
+        #[strategy(0_usize..=16)] count: usize,
+        container: Size,
+    ) {
+        prop_assert_eq!(
+            AlignEdges::new(container, tolerance, count).evaluate(
+                &repeat_n(Rect::new(0, 0, container.width, container.height), count).collect_vec()
+            ),
+            0.0
+        )
+    }
+
+    #[test]
+    fn align_edges_returns_0_for_tilings_with_fewest_edges() {
+        let container = Size::new_checked(10, 10);
+        let columns = [
+            Rect::new_checked(0, 0, 3, 10),
+            Rect::new_checked(3, 0, 7, 10),
+        ];
+        assert_eq!(
+            AlignEdges::new(container, 1, columns.len()).evaluate(&columns),
+            0.0
+        );
+        let grid = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(5, 0, 5, 5),
+            Rect::new_checked(0, 5, 10, 5),
+        ];
+        assert_eq!(
+            AlignEdges::new(container, 1, grid.len()).evaluate(&grid),
+            0.0
+        );
+    }
+
+    #[test]
+    fn align_edges_prefers_shared_edges() {
+        let container = Size::new_checked(10, 10);
+        let aligned = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(5, 0, 5, 5),
+            Rect::new_checked(0, 5, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+        ];
+        let misaligned = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(5, 0, 5, 4),
+            Rect::new_checked(0, 5, 4, 5),
+            Rect::new_checked(4, 4, 6, 6),
+        ];
+        let objective = AlignEdges::new(container, 2, 4);
+        assert!(objective.evaluate(&aligned) < objective.evaluate(&misaligned))
+    }
+}
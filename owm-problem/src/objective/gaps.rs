@@ -1,29 +1,39 @@
 use std::num::NonZeroUsize;
 
+use itertools::Itertools;
+
 use crate::{rect::covered_area, Rect, Size};
 
 pub struct MinimizeGaps {
     area: NonZeroUsize,
+    reserved: Vec<Rect>,
     worst_case: f64,
 }
 
 impl MinimizeGaps {
-    pub fn new(container: Size) -> Self {
+    /// Area in `reserved` does not count as a gap.
+    pub fn new(container: Size, reserved: Vec<Rect>) -> Self {
         Self {
             area: container.area(),
-            worst_case: (container.area().get() - 1) as f64,
+            worst_case: (container.area().get() - covered_area(&reserved).max(1)) as f64,
+            reserved,
         }
     }
 
     pub fn evaluate(&self, rects: &[Rect]) -> f64 {
         if rects.is_empty() {
             1.0
+        } else if self.worst_case == 0.0 {
+            // No gap is possible
+            // if `container.area()` is `1`
+            // or `reserved` covers the container.
+            0.0
         } else {
             // This assumes rectangles do not exceed container bounds.
-            // Worst case can theoretically be zero,
-            // if `container.area()` is `1`,
-            // but this is unrealistic in practice.
-            (self.area.get() - covered_area(rects)) as f64 / self.worst_case
+            (self.area.get()
+                - covered_area(&rects.iter().chain(&self.reserved).copied().collect_vec()))
+                as f64
+                / self.worst_case
         }
     }
 }
@@ -42,7 +52,9 @@ mod tests {
 
     #[proptest]
     fn minimize_gaps_returns_values_in_range_0_1(x: ContainedRects) {
-        prop_assert!((0.0..=1.0).contains(&MinimizeGaps::new(x.container).evaluate(&x.rects)))
+        prop_assert!(
+            (0.0..=1.0).contains(&MinimizeGaps::new(x.container, Vec::new()).evaluate(&x.rects))
+        )
     }
 
     #[proptest]
@@ -52,7 +64,7 @@ mod tests {
     ) {
         prop_assume!(container.width.get() > 1 || container.height.get() > 1);
         prop_assert_eq!(
-            MinimizeGaps::new(container).evaluate(
+            MinimizeGaps::new(container, Vec::new()).evaluate(
                 &repeat(Rect::new_checked(0, 0, 1, 1))
                     .take(count)
                     .collect_vec()
@@ -69,13 +81,35 @@ mod tests {
             Rect::new_checked(0, 5, 5, 5),
             Rect::new_checked(5, 5, 5, 5),
         ];
-        assert_eq!(MinimizeGaps::new(container).evaluate(&rects), 0.0)
+        assert_eq!(
+            MinimizeGaps::new(container, Vec::new()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn minimize_gaps_returns_0_for_best_case_around_reserved() {
+        let container = Size::new_checked(10, 10);
+        let reserved = vec![Rect::new_checked(5, 5, 5, 5)];
+        let rects = [
+            Rect::new_checked(0, 0, 10, 5),
+            Rect::new_checked(0, 5, 5, 5),
+        ];
+        assert_eq!(MinimizeGaps::new(container, reserved).evaluate(&rects), 0.0)
+    }
+
+    #[test]
+    fn minimize_gaps_returns_0_when_reserved_covers_container() {
+        let container = Size::new_checked(10, 10);
+        let reserved = vec![Rect::new_checked(0, 0, 10, 10)];
+        let rects = [Rect::new_checked(0, 0, 5, 5)];
+        assert_eq!(MinimizeGaps::new(container, reserved).evaluate(&rects), 0.0)
     }
 
     #[proptest]
     fn minimize_gaps_returns_0_for_best_case_with_overlap(x: ContainedRects) {
         prop_assert_eq!(
-            MinimizeGaps::new(x.container).evaluate(
+            MinimizeGaps::new(x.container, Vec::new()).evaluate(
                 &once(Rect::new(0, 0, x.container.width, x.container.height))
                     .chain(x.rects)
                     .collect_vec()
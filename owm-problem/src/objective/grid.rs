@@ -0,0 +1,181 @@
+use std::{fmt, num::NonZeroUsize, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::Rect;
+
+/// Organize windows into a number of columns and rows.
+pub struct PreferGrid {
+    columns: GridCount,
+    rows: GridCount,
+    worst_case: f64,
+}
+
+/// A desired number of columns or rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridCount {
+    /// No preference.
+    Any,
+    Exactly(NonZeroUsize),
+}
+
+/// Error returned when failing to parse 'GridCount'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not 'any' or a positive integer")]
+pub struct GridCountFromStrError(String);
+
+impl FromStr for GridCount {
+    type Err = GridCountFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Self::Any),
+            _ => s
+                .parse()
+                .map(Self::Exactly)
+                .map_err(|_| GridCountFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for GridCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::Exactly(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl GridCount {
+    /// Return how far `actual` is from this count,
+    /// or `0` if there is no preference.
+    fn deviation(self, actual: usize) -> usize {
+        match self {
+            Self::Any => 0,
+            Self::Exactly(x) => x.get().abs_diff(actual),
+        }
+    }
+}
+
+impl PreferGrid {
+    pub fn new(columns: GridCount, rows: GridCount, count: usize) -> Self {
+        // A layout has between `1` and `count` columns and rows.
+        let worst_case_for = |target: GridCount| match target {
+            GridCount::Any => 0,
+            GridCount::Exactly(x) => (x.get() - 1).max(count.saturating_sub(x.get())),
+        };
+        Self {
+            columns,
+            rows,
+            worst_case: (worst_case_for(columns) + worst_case_for(rows)) as f64,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 || rects.is_empty() {
+            0.0
+        } else {
+            // Windows sharing a left edge are in the same column,
+            // and windows sharing a top edge are in the same row.
+            (self
+                .columns
+                .deviation(rects.iter().map(|rect| rect.left()).unique().count())
+                + self
+                    .rows
+                    .deviation(rects.iter().map(|rect| rect.top()).unique().count()))
+                as f64
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn prefer_grid_returns_values_in_range_0_1(
+        #[strategy(arbitrary_grid_count())] columns: GridCount,
+        #[strategy(arbitrary_grid_count())] rows: GridCount,
+        x: ContainedRects,
+    ) {
+        prop_assert!(
+            (0.0..=1.0).contains(&PreferGrid::new(columns, rows, x.rects.len()).evaluate(&x.rects))
+        )
+    }
+
+    #[test]
+    fn prefer_grid_returns_1_for_worst_case() {
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+        ];
+        assert_eq!(
+            PreferGrid::new(exactly(3), exactly(3), rects.len()).evaluate(&rects),
+            1.0
+        );
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(1, 1, 1, 1),
+            Rect::new_checked(2, 2, 1, 1),
+        ];
+        assert_eq!(
+            PreferGrid::new(exactly(1), exactly(1), rects.len()).evaluate(&rects),
+            1.0
+        );
+    }
+
+    #[test]
+    fn prefer_grid_returns_0_for_best_case() {
+        // ```
+        // 0 2 3
+        // 1 2 4
+        // ```
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 1, 1, 1),
+            Rect::new_checked(1, 0, 1, 2),
+            Rect::new_checked(2, 0, 1, 1),
+            Rect::new_checked(2, 1, 1, 1),
+        ];
+        assert_eq!(
+            PreferGrid::new(exactly(3), exactly(2), rects.len()).evaluate(&rects),
+            0.0
+        );
+        assert_eq!(
+            PreferGrid::new(exactly(3), GridCount::Any, rects.len()).evaluate(&rects),
+            0.0
+        );
+    }
+
+    #[proptest]
+    fn prefer_grid_returns_0_without_preference(x: ContainedRects) {
+        prop_assert_eq!(
+            PreferGrid::new(GridCount::Any, GridCount::Any, x.rects.len()).evaluate(&x.rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn grid_count_round_trips_through_strings() {
+        for count in [GridCount::Any, exactly(3)] {
+            assert_eq!(count.to_string().parse::<GridCount>(), Ok(count))
+        }
+        assert!("0".parse::<GridCount>().is_err());
+    }
+
+    fn exactly(x: usize) -> GridCount {
+        GridCount::Exactly(NonZeroUsize::new(x).unwrap())
+    }
+
+    fn arbitrary_grid_count() -> impl Strategy<Value = GridCount> {
+        prop_oneof![Just(GridCount::Any), (1_usize..=16).prop_map(exactly)]
+    }
+}
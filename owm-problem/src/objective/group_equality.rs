@@ -0,0 +1,204 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+use crate::{post_processing::div_ceil, Rect, Size};
+
+/// Keep windows within each group
+/// roughly equal in area.
+pub struct EqualizeGroups {
+    groups: Vec<IndexGroup>,
+    worst_case: f64,
+}
+
+/// A range of window indices,
+/// open-ended if `end` is `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexGroup {
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+/// Error returned when failing to parse 'IndexGroup'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+pub enum IndexGroupFromStrError {
+    /// Value is not a range.
+    #[error("'{0}' is not of the form 'START..END' or 'START..'")]
+    NotRange(String),
+    /// A bound is not an index.
+    #[error("{0}")]
+    Index(ParseIntError),
+    /// Range has fewer than two indices.
+    #[error("'{0}' does not contain at least two indices")]
+    TooSmall(String),
+}
+
+impl FromStr for IndexGroup {
+    type Err = IndexGroupFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once("..") {
+            Some((start, end)) => {
+                let group = Self {
+                    start: start.parse().map_err(Self::Err::Index)?,
+                    end: if end.is_empty() {
+                        None
+                    } else {
+                        Some(end.parse().map_err(Self::Err::Index)?)
+                    },
+                };
+                match group.end {
+                    Some(end) if end < group.start + 2 => Err(Self::Err::TooSmall(s.to_owned())),
+                    _ => Ok(group),
+                }
+            }
+            None => Err(Self::Err::NotRange(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for IndexGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}..{end}", self.start),
+            None => write!(f, "{}..", self.start),
+        }
+    }
+}
+
+impl IndexGroup {
+    /// Return the number of indices in this group
+    /// less than `count`.
+    fn len(&self, count: usize) -> usize {
+        self.end
+            .map_or(count, |end| end.min(count))
+            .saturating_sub(self.start)
+    }
+
+    /// Return whether `index` is in this group.
+    pub fn contains(&self, index: usize) -> bool {
+        (self.start..self.end.unwrap_or(usize::MAX)).contains(&index)
+    }
+
+    /// Return the part of `xs` in this group.
+    fn of<'a, T>(&self, xs: &'a [T]) -> &'a [T] {
+        &xs[self.start.min(xs.len())..][..self.len(xs.len())]
+    }
+}
+
+impl EqualizeGroups {
+    pub fn new(groups: Vec<IndexGroup>, max_size: Size, count: usize) -> Self {
+        // Absolute deviation from the mean is greatest
+        // when half the windows have min area
+        // and half have max area.
+        let max_deviation = (max_size.area().get() - 1) as f64;
+        let worst_case = groups
+            .iter()
+            .map(|group| {
+                let n = group.len(count);
+                (2 * (n / 2) * div_ceil(n, 2)) as f64 / n.max(1) as f64 * max_deviation
+            })
+            .sum();
+        Self { groups, worst_case }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            self.groups
+                .iter()
+                .map(|group| {
+                    let areas = group
+                        .of(rects)
+                        .iter()
+                        .map(|rect| rect.area().get() as f64)
+                        .collect::<Vec<_>>();
+                    let mean = areas.iter().sum::<f64>() / areas.len().max(1) as f64;
+                    areas.iter().map(|area| (area - mean).abs()).sum::<f64>()
+                })
+                .sum::<f64>()
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn equalize_groups_returns_values_in_range_0_1(
+        #[strategy(vec(arbitrary_index_group(), 0..4))] groups: Vec<IndexGroup>,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0)
+            .contains(&EqualizeGroups::new(groups, x.container, x.rects.len()).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn equalize_groups_returns_1_for_worst_case() {
+        let container = Size::new_checked(10, 10);
+        let groups = vec![IndexGroup {
+            start: 1,
+            end: None,
+        }];
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 10, 10),
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 10, 10),
+            Rect::new_checked(0, 0, 1, 1),
+        ];
+        assert_eq!(
+            EqualizeGroups::new(groups, container, rects.len()).evaluate(&rects),
+            1.0
+        )
+    }
+
+    #[test]
+    fn equalize_groups_returns_0_for_best_case() {
+        let container = Size::new_checked(10, 10);
+        let groups = vec![IndexGroup {
+            start: 1,
+            end: None,
+        }];
+        let rects = [
+            Rect::new_checked(0, 0, 5, 10),
+            Rect::new_checked(5, 0, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+        ];
+        assert_eq!(
+            EqualizeGroups::new(groups, container, rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn index_group_round_trips_through_strings() {
+        for group in [
+            IndexGroup {
+                start: 1,
+                end: None,
+            },
+            IndexGroup {
+                start: 0,
+                end: Some(3),
+            },
+        ] {
+            assert_eq!(group.to_string().parse::<IndexGroup>(), Ok(group))
+        }
+        assert!("1".parse::<IndexGroup>().is_err());
+        assert!("2..3".parse::<IndexGroup>().is_err());
+    }
+
+    fn arbitrary_index_group() -> impl Strategy<Value = IndexGroup> {
+        (0_usize..8, proptest::option::of(2_usize..8)).prop_map(|(start, len)| IndexGroup {
+            start,
+            end: len.map(|len| start + len),
+        })
+    }
+}
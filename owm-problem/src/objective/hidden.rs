@@ -0,0 +1,140 @@
+use derive_more::Display;
+use itertools::Itertools;
+use num_traits::bounds::Bounded;
+
+use crate::{
+    derive::{
+        derive_from_str_from_try_into, derive_new_from_bounded_float, derive_try_from_from_new,
+    },
+    rect::covered_area,
+    Rect,
+};
+
+/// Steeply penalize windows
+/// mostly covered by other windows.
+pub struct AvoidHidden {
+    min_visible: VisibleFraction,
+    worst_case: f64,
+}
+
+/// Fraction of a window's area
+/// not covered by other windows.
+#[derive(Clone, Copy, Debug, Display, PartialEq, PartialOrd)]
+pub struct VisibleFraction(f64);
+
+impl Bounded for VisibleFraction {
+    fn min_value() -> Self {
+        Self(0.0)
+    }
+
+    fn max_value() -> Self {
+        Self(1.0)
+    }
+}
+
+derive_new_from_bounded_float!(VisibleFraction(f64));
+derive_try_from_from_new!(VisibleFraction(f64));
+derive_from_str_from_try_into!(VisibleFraction(f64));
+
+impl AvoidHidden {
+    pub fn new(min_visible: VisibleFraction, count: usize) -> Self {
+        Self {
+            min_visible,
+            worst_case: count as f64,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if rects.len() < 2 {
+            0.0
+        } else {
+            rects
+                .iter()
+                .enumerate()
+                .map(|(i, rect)| {
+                    let covered = covered_area(
+                        &rects
+                            .iter()
+                            .enumerate()
+                            .filter(|(other_i, _)| i != *other_i)
+                            .filter_map(|(_, other)| rect.overlap(other))
+                            .collect_vec(),
+                    );
+                    let visible = 1.0 - covered as f64 / rect.area().get() as f64;
+                    if visible < self.min_visible.0 {
+                        // Any hidden window is much worse
+                        // than none,
+                        // and more hidden is worse still.
+                        0.5 + 0.5 * (self.min_visible.0 - visible) / self.min_visible.0
+                    } else {
+                        0.0
+                    }
+                })
+                .sum::<f64>()
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::repeat_n;
+
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::{testing::ContainedRects, Size};
+
+    use super::*;
+
+    #[proptest]
+    fn avoid_hidden_returns_values_in_range_0_1(
+        #[strategy(0.0..=1.0)] min_visible: f64,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0).contains(
+            &AvoidHidden::new(VisibleFraction::new(min_visible).unwrap(), x.rects.len())
+                .evaluate(&x.rects)
+        ))
+    }
+
+    #[proptest]
+    fn avoid_hidden_returns_1_for_worst_case(
+        #[strategy(0.01..=1.0)] min_visible: f64,
+        container: Size,
+        #[strategy((2_usize..=16))] count: usize,
+    ) {
+        prop_assert_eq!(
+            AvoidHidden::new(VisibleFraction::new(min_visible).unwrap(), count).evaluate(
+                &repeat_n(Rect::new(0, 0, container.width, container.height), count).collect_vec()
+            ),
+            1.0
+        )
+    }
+
+    #[test]
+    fn avoid_hidden_returns_0_for_best_case() {
+        let rects = [
+            Rect::new_checked(0, 0, 6, 10),
+            Rect::new_checked(4, 0, 6, 10),
+        ];
+        assert_eq!(
+            AvoidHidden::new(VisibleFraction::new(0.5).unwrap(), rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn avoid_hidden_prefers_slight_overlap_to_hidden_window() {
+        let hidden = [
+            Rect::new_checked(0, 0, 10, 10),
+            Rect::new_checked(0, 0, 1, 1),
+        ];
+        let overlapping = [
+            Rect::new_checked(0, 0, 6, 10),
+            Rect::new_checked(4, 0, 6, 10),
+        ];
+        let objective = AvoidHidden::new(VisibleFraction::new(0.25).unwrap(), 2);
+        assert!(objective.evaluate(&hidden) > objective.evaluate(&overlapping))
+    }
+}
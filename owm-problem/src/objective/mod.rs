@@ -3,9 +3,20 @@ mod area_ratios;
 mod aspect_ratios;
 mod center_main;
 mod consistency;
+mod constraint;
+mod containment;
+mod edge_alignment;
 mod gaps;
+mod grid;
+mod group_equality;
+mod hidden;
 mod overlap;
+mod readable;
 mod reading_order;
+mod reserved;
+mod size_order;
+mod sliceable;
+mod symmetry;
 
 use std::ops::Mul;
 
@@ -20,9 +31,26 @@ use crate::{
 use self::{
     adjacent_close::PlaceAdjacentClose, area_ratios::MaintainAreaRatios,
     aspect_ratios::MaintainAspectRatios, center_main::CenterMain, consistency::MaximizeConsistency,
-    gaps::MinimizeGaps, overlap::MinimizeOverlap, reading_order::PlaceInReadingOrder,
+    containment::StayContained, edge_alignment::AlignEdges, gaps::MinimizeGaps, grid::PreferGrid,
+    group_equality::EqualizeGroups, hidden::AvoidHidden, overlap::MinimizeOverlap,
+    readable::KeepReadable, reading_order::PlaceInReadingOrder, reserved::AvoidReserved,
+    size_order::KeepSizesOrdered, sliceable::PreferSliceable, symmetry::MaximizeSymmetry,
+};
+pub use self::{
+    adjacent_close::{Adjacency, AdjacencyFromStrError},
+    area_ratios::AreaRatio,
+    aspect_ratios::AspectRatio,
+    center_main::{MainAnchor, MainAnchorFromStrError},
+    constraint::{Constraint, ConstraintFromStrError, Objective, ObjectiveFromStrError},
+    grid::{GridCount, GridCountFromStrError},
+    group_equality::{IndexGroup, IndexGroupFromStrError},
+    hidden::{InvalidVisibleFractionError, VisibleFraction, VisibleFractionFromStrError},
+    reading_order::{
+        ReadingDirection, ReadingDirectionFromStrError, ReadingMajor, ReadingMajorFromStrError,
+        ReadingOrder,
+    },
+    symmetry::{SymmetryAxes, SymmetryAxesFromStrError},
 };
-pub use self::{area_ratios::AreaRatio, aspect_ratios::AspectRatio};
 
 pub struct Problem {
     weights: Weights,
@@ -34,6 +62,48 @@ pub struct Problem {
     reading_order: PlaceInReadingOrder,
     center_main: CenterMain,
     consistency: MaximizeConsistency,
+    symmetry: MaximizeSymmetry,
+    edge_alignment: AlignEdges,
+    reserved: AvoidReserved,
+    grid: PreferGrid,
+    sliceable: PreferSliceable,
+    group_equality: EqualizeGroups,
+    readable: KeepReadable,
+    hidden: AvoidHidden,
+    constraints: Vec<Constraint>,
+    containment: StayContained,
+    min_sizes: KeepReadable,
+    size_order: KeepSizesOrdered,
+}
+
+/// Everything needed to make a `Problem`
+/// for the layout after `prev_layout`.
+#[derive(Clone, Debug)]
+pub struct ProblemParams {
+    pub weights: Weights,
+    /// Objectives in `constraints`
+    /// are no longer weighted.
+    pub constraints: Vec<Constraint>,
+    pub area_ratios: Vec<AreaRatio>,
+    pub aspect_ratios: Vec<AspectRatio>,
+    pub equal_groups: Vec<IndexGroup>,
+    pub main_anchor: MainAnchor,
+    /// Index of the window
+    /// given the main window's area and anchor.
+    pub main: usize,
+    pub adjacency: Adjacency,
+    pub reading_order: ReadingOrder,
+    pub symmetry_axes: SymmetryAxes,
+    pub edge_alignment_tolerance: usize,
+    pub grid_columns: GridCount,
+    pub grid_rows: GridCount,
+    pub readable_sizes: Vec<Size>,
+    pub min_visible: VisibleFraction,
+    pub min_sizes: Vec<Size>,
+    pub max_size: Size,
+    pub container: Size,
+    pub reserved: Vec<Rect>,
+    pub prev_layout: Vec<Rect>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -46,6 +116,14 @@ pub struct Weights {
     pub reading_order_weight: Weight,
     pub center_main_weight: Weight,
     pub consistency_weight: Weight,
+    pub symmetry_weight: Weight,
+    pub edge_alignment_weight: Weight,
+    pub reserved_weight: Weight,
+    pub grid_weight: Weight,
+    pub sliceable_weight: Weight,
+    pub group_equality_weight: Weight,
+    pub readable_weight: Weight,
+    pub hidden_weight: Weight,
 }
 
 #[derive(Clone, Copy, Debug, Display, PartialEq, PartialOrd)]
@@ -69,62 +147,338 @@ impl Mul<f64> for Weight {
     }
 }
 
+impl Weights {
+    pub fn get(&self, objective: Objective) -> Weight {
+        match objective {
+            Objective::Gaps => self.gaps_weight,
+            Objective::Overlap => self.overlap_weight,
+            Objective::AreaRatios => self.area_ratios_weight,
+            Objective::AspectRatios => self.aspect_ratios_weight,
+            Objective::AdjacentClose => self.adjacent_close_weight,
+            Objective::ReadingOrder => self.reading_order_weight,
+            Objective::CenterMain => self.center_main_weight,
+            Objective::Consistency => self.consistency_weight,
+            Objective::Symmetry => self.symmetry_weight,
+            Objective::EdgeAlignment => self.edge_alignment_weight,
+            Objective::Reserved => self.reserved_weight,
+            Objective::Grid => self.grid_weight,
+            Objective::Sliceable => self.sliceable_weight,
+            Objective::GroupEquality => self.group_equality_weight,
+            Objective::Readable => self.readable_weight,
+            Objective::Hidden => self.hidden_weight,
+        }
+    }
+
+    pub fn get_mut(&mut self, objective: Objective) -> &mut Weight {
+        match objective {
+            Objective::Gaps => &mut self.gaps_weight,
+            Objective::Overlap => &mut self.overlap_weight,
+            Objective::AreaRatios => &mut self.area_ratios_weight,
+            Objective::AspectRatios => &mut self.aspect_ratios_weight,
+            Objective::AdjacentClose => &mut self.adjacent_close_weight,
+            Objective::ReadingOrder => &mut self.reading_order_weight,
+            Objective::CenterMain => &mut self.center_main_weight,
+            Objective::Consistency => &mut self.consistency_weight,
+            Objective::Symmetry => &mut self.symmetry_weight,
+            Objective::EdgeAlignment => &mut self.edge_alignment_weight,
+            Objective::Reserved => &mut self.reserved_weight,
+            Objective::Grid => &mut self.grid_weight,
+            Objective::Sliceable => &mut self.sliceable_weight,
+            Objective::GroupEquality => &mut self.group_equality_weight,
+            Objective::Readable => &mut self.readable_weight,
+            Objective::Hidden => &mut self.hidden_weight,
+        }
+    }
+
+    /// Return the highest possible weighted sum of objectives,
+    /// each being at most `1`.
+    fn total(&self) -> f64 {
+        Objective::ALL
+            .into_iter()
+            .map(|objective| self.get(objective).0)
+            .sum()
+    }
+}
+
 impl Problem {
-    pub fn new(
-        weights: Weights,
-        area_ratios: Vec<AreaRatio>,
-        aspect_ratios: Vec<AspectRatio>,
-        max_size: Size,
-        container: Size,
-        prev_layout: Vec<Rect>,
-    ) -> Self {
+    pub fn new(params: ProblemParams) -> Self {
+        let ProblemParams {
+            weights,
+            constraints,
+            area_ratios,
+            aspect_ratios,
+            equal_groups,
+            main_anchor,
+            main,
+            adjacency,
+            reading_order,
+            symmetry_axes,
+            edge_alignment_tolerance,
+            grid_columns,
+            grid_rows,
+            readable_sizes,
+            min_visible,
+            min_sizes,
+            max_size,
+            container,
+            reserved,
+            prev_layout,
+        } = params;
         let count = prev_layout.len() + 1;
         Self {
             weights,
-            gaps: MinimizeGaps::new(container),
+            gaps: MinimizeGaps::new(container, reserved.clone()),
             overlap: MinimizeOverlap::new(container, count),
-            area_ratios: MaintainAreaRatios::new(area_ratios, max_size, count),
+            area_ratios: MaintainAreaRatios::new(area_ratios, main, max_size, count),
             aspect_ratios: MaintainAspectRatios::new(aspect_ratios, max_size, count),
-            adjacent_close: PlaceAdjacentClose::new(container, count),
-            reading_order: PlaceInReadingOrder::new(count),
-            center_main: CenterMain::new(container),
+            adjacent_close: PlaceAdjacentClose::new(adjacency, container, count),
+            reading_order: PlaceInReadingOrder::new(reading_order, count),
+            center_main: CenterMain::new(main_anchor, main, container),
+            symmetry: MaximizeSymmetry::new(symmetry_axes, container, count),
+            edge_alignment: AlignEdges::new(container, edge_alignment_tolerance, count),
+            reserved: AvoidReserved::new(reserved, count),
+            grid: PreferGrid::new(grid_columns, grid_rows, count),
+            sliceable: PreferSliceable::new(count),
+            group_equality: EqualizeGroups::new(equal_groups, max_size, count),
+            readable: KeepReadable::new(readable_sizes),
+            hidden: AvoidHidden::new(min_visible, count),
+            constraints,
+            containment: StayContained::new(container),
+            min_sizes: KeepReadable::new(min_sizes),
+            size_order: KeepSizesOrdered::new(max_size, count),
             consistency: MaximizeConsistency::new(container, prev_layout),
         }
     }
 
     pub fn evaluate(&self, rects: &[Rect]) -> f64 {
-        (if self.weights.gaps_weight > Weight(0.0) {
-            self.weights.gaps_weight * self.gaps.evaluate(rects)
+        // Other measures assume windows are contained,
+        // so they are not evaluated
+        // for windows outside the container.
+        let violation = if self.constraints.contains(&Constraint::Containment) {
+            self.containment.evaluate(rects)
         } else {
             0.0
-        }) + (if self.weights.overlap_weight > Weight(0.0) {
-            self.weights.overlap_weight * self.overlap.evaluate(rects)
+        };
+        let violation = if violation > 0.0 {
+            violation
         } else {
-            0.0
-        }) + (if self.weights.area_ratios_weight > Weight(0.0) {
-            self.weights.area_ratios_weight * self.area_ratios.evaluate(rects)
-        } else {
-            0.0
-        }) + (if self.weights.aspect_ratios_weight > Weight(0.0) {
-            self.weights.aspect_ratios_weight * self.aspect_ratios.evaluate(rects)
-        } else {
-            0.0
-        }) + (if self.weights.adjacent_close_weight > Weight(0.0) {
-            self.weights.adjacent_close_weight * self.adjacent_close.evaluate(rects)
-        } else {
-            0.0
-        }) + (if self.weights.reading_order_weight > Weight(0.0) {
-            self.weights.reading_order_weight * self.reading_order.evaluate(rects)
-        } else {
-            0.0
-        }) + (if self.weights.center_main_weight > Weight(0.0) {
-            self.weights.center_main_weight * self.center_main.evaluate(rects)
+            self.constraints
+                .iter()
+                .map(|constraint| self.evaluate_constraint(*constraint, rects))
+                .sum::<f64>()
+        };
+        if violation > 0.0 {
+            // Infeasible layouts are worse
+            // than any feasible layout
+            // and ranked by violation alone,
+            // so less violation always wins.
+            self.weights.total() + violation
         } else {
-            0.0
-        }) + (if self.weights.consistency_weight > Weight(0.0) {
-            self.weights.consistency_weight * self.consistency.evaluate(rects)
+            Objective::ALL
+                .into_iter()
+                .filter(|objective| {
+                    !self
+                        .constraints
+                        .contains(&Constraint::Objective(*objective))
+                })
+                .map(|objective| {
+                    let weight = self.weights.get(objective);
+                    if weight > Weight(0.0) {
+                        weight * self.evaluate_objective(objective, rects)
+                    } else {
+                        0.0
+                    }
+                })
+                .sum::<f64>()
+        }
+    }
+
+    /// Return the unweighted value of each objective,
+    /// for comparing layouts
+    /// without choosing weights.
+    pub fn evaluate_each(&self, rects: &[Rect]) -> Vec<(Objective, f64)> {
+        Objective::ALL
+            .into_iter()
+            .map(|objective| (objective, self.evaluate_objective(objective, rects)))
+            .collect()
+    }
+
+    fn evaluate_objective(&self, objective: Objective, rects: &[Rect]) -> f64 {
+        match objective {
+            Objective::Gaps => self.gaps.evaluate(rects),
+            Objective::Overlap => self.overlap.evaluate(rects),
+            Objective::AreaRatios => self.area_ratios.evaluate(rects),
+            Objective::AspectRatios => self.aspect_ratios.evaluate(rects),
+            Objective::AdjacentClose => self.adjacent_close.evaluate(rects),
+            Objective::ReadingOrder => self.reading_order.evaluate(rects),
+            Objective::CenterMain => self.center_main.evaluate(rects),
+            Objective::Consistency => self.consistency.evaluate(rects),
+            Objective::Symmetry => self.symmetry.evaluate(rects),
+            Objective::EdgeAlignment => self.edge_alignment.evaluate(rects),
+            Objective::Reserved => self.reserved.evaluate(rects),
+            Objective::Grid => self.grid.evaluate(rects),
+            Objective::Sliceable => self.sliceable.evaluate(rects),
+            Objective::GroupEquality => self.group_equality.evaluate(rects),
+            Objective::Readable => self.readable.evaluate(rects),
+            Objective::Hidden => self.hidden.evaluate(rects),
+        }
+    }
+
+    fn evaluate_constraint(&self, constraint: Constraint, rects: &[Rect]) -> f64 {
+        match constraint {
+            Constraint::Objective(objective) => self.evaluate_objective(objective, rects),
+            Constraint::Containment => self.containment.evaluate(rects),
+            Constraint::MinSizes => self.min_sizes.evaluate(rects),
+            Constraint::SizeOrder => self.size_order.evaluate(rects),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::{
+        rect::obscured_area,
+        testing::{ContainedRects, ContainedRectsParams},
+    };
+
+    use super::*;
+
+    #[proptest]
+    fn problem_ranks_feasible_layouts_above_infeasible(
+        #[strategy(arbitrary_layout_pair())] layouts: (Size, Vec<Rect>, Vec<Rect>),
+    ) {
+        let (container, x, y) = layouts;
+        let problem = problem(
+            container,
+            x.len(),
+            vec![Constraint::Objective(Objective::Overlap)],
+        );
+        let is_feasible = |rects: &[Rect]| obscured_area(rects) == 0;
+        prop_assume!(is_feasible(&x) != is_feasible(&y));
+        if is_feasible(&x) {
+            prop_assert!(problem.evaluate(&x) < problem.evaluate(&y))
         } else {
-            0.0
+            prop_assert!(problem.evaluate(&x) > problem.evaluate(&y))
+        }
+    }
+
+    #[proptest]
+    fn problem_ranks_infeasible_layouts_by_violation(
+        #[strategy(arbitrary_layout_pair())] layouts: (Size, Vec<Rect>, Vec<Rect>),
+    ) {
+        let (container, x, y) = layouts;
+        let constraint = Constraint::Objective(Objective::Overlap);
+        let problem = problem(container, x.len(), vec![constraint]);
+        let x_violation = problem.evaluate_constraint(constraint, &x);
+        let y_violation = problem.evaluate_constraint(constraint, &y);
+        prop_assume!(x_violation > 0.0 && y_violation > 0.0);
+        prop_assume!(x_violation != y_violation);
+        prop_assert_eq!(
+            problem.evaluate(&x) < problem.evaluate(&y),
+            x_violation < y_violation
+        )
+    }
+
+    #[test]
+    fn problem_prefers_less_violation() {
+        let container = Size::new_checked(10, 10);
+        let problem = problem(
+            container,
+            2,
+            vec![Constraint::Objective(Objective::Overlap)],
+        );
+        assert!(
+            problem.evaluate(&[
+                Rect::new_checked(0, 0, 6, 10),
+                Rect::new_checked(4, 0, 6, 10)
+            ]) < problem.evaluate(&[
+                Rect::new_checked(0, 0, 10, 10),
+                Rect::new_checked(0, 0, 10, 10)
+            ])
+        )
+    }
+
+    #[test]
+    fn problem_ranks_layouts_outside_container_below_contained() {
+        let container = Size::new_checked(10, 10);
+        let problem = problem(
+            container,
+            2,
+            vec![
+                Constraint::Containment,
+                Constraint::Objective(Objective::Gaps),
+            ],
+        );
+        let contained = [
+            Rect::new_checked(0, 0, 10, 10),
+            Rect::new_checked(0, 0, 10, 10),
+        ];
+        let outside = [
+            Rect::new_checked(0, 0, 5, 10),
+            Rect::new_checked(5, 0, 10, 10),
+        ];
+        assert!(problem.evaluate_constraint(Constraint::Containment, &outside) > 0.0);
+        assert!(problem.evaluate(&outside) > problem.evaluate(&contained))
+    }
+
+    fn arbitrary_layout_pair() -> impl Strategy<Value = (Size, Vec<Rect>, Vec<Rect>)> {
+        ContainedRects::arbitrary_with(ContainedRectsParams::from_len_range(2..=8))
+            .prop_flat_map(|x| {
+                (
+                    ContainedRects::arbitrary_with(ContainedRectsParams {
+                        width_range: x.container.width..=x.container.width,
+                        height_range: x.container.height..=x.container.height,
+                        len_range: x.rects.len()..=x.rects.len(),
+                    }),
+                    Just(x),
+                )
+            })
+            .prop_map(|(y, x)| (x.container, x.rects, y.rects))
+    }
+
+    fn problem(container: Size, count: usize, constraints: Vec<Constraint>) -> Problem {
+        let weight = Weight::new(1.0).unwrap();
+        Problem::new(ProblemParams {
+            weights: Weights {
+                gaps_weight: weight,
+                overlap_weight: weight,
+                area_ratios_weight: weight,
+                aspect_ratios_weight: weight,
+                adjacent_close_weight: weight,
+                reading_order_weight: weight,
+                center_main_weight: weight,
+                consistency_weight: weight,
+                symmetry_weight: weight,
+                edge_alignment_weight: weight,
+                reserved_weight: weight,
+                grid_weight: weight,
+                sliceable_weight: weight,
+                group_equality_weight: weight,
+                readable_weight: weight,
+                hidden_weight: weight,
+            },
+            constraints,
+            area_ratios: vec![AreaRatio::new(2.0).unwrap()],
+            aspect_ratios: vec![AspectRatio::new(1.0).unwrap()],
+            equal_groups: vec!["1..".parse().unwrap()],
+            main_anchor: MainAnchor::Center,
+            main: 0,
+            adjacency: Adjacency::Corners,
+            reading_order: ReadingOrder::default(),
+            symmetry_axes: SymmetryAxes::Horizontal,
+            edge_alignment_tolerance: 0,
+            grid_columns: GridCount::Any,
+            grid_rows: GridCount::Any,
+            readable_sizes: vec![Size::new_checked(1, 1); count],
+            min_visible: VisibleFraction::new(0.25).unwrap(),
+            min_sizes: vec![Size::new_checked(1, 1); count],
+            max_size: container,
+            container,
+            reserved: Vec::new(),
+            prev_layout: vec![Rect::new(0, 0, container.width, container.height); count - 1],
         })
     }
 }
@@ -0,0 +1,92 @@
+use crate::{Rect, Size};
+
+/// Keep windows at least as large
+/// as needed to be readable,
+/// like 80 by 24 cells of text.
+pub struct KeepReadable {
+    min_sizes: Vec<Size>,
+    worst_case: f64,
+}
+
+impl KeepReadable {
+    /// `min_sizes` are in pixels,
+    /// one for each window.
+    /// A length of `1` means no minimum.
+    pub fn new(min_sizes: Vec<Size>) -> Self {
+        Self {
+            // Shortfall along each axis is at most `min - 1`,
+            // normalized to `1`.
+            worst_case: min_sizes
+                .iter()
+                .flat_map(|size| [size.width.get(), size.height.get()])
+                .filter(|x| *x > 1)
+                .count() as f64,
+            min_sizes,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            rects
+                .iter()
+                .zip(&self.min_sizes)
+                .flat_map(|(rect, min_size)| {
+                    [
+                        shortfall(rect.width().get(), min_size.width.get()),
+                        shortfall(rect.height().get(), min_size.height.get()),
+                    ]
+                })
+                .sum::<f64>()
+                / self.worst_case
+        }
+    }
+}
+
+/// Return how far `length` is below `min`,
+/// in range 0..=1.
+fn shortfall(length: usize, min: usize) -> f64 {
+    if length >= min {
+        0.0
+    } else {
+        (min - length) as f64 / (min - 1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn keep_readable_returns_values_in_range_0_1(
+        #[strategy(1_usize..=1000)] min_width: usize,
+        #[strategy(1_usize..=1000)] min_height: usize,
+        x: ContainedRects,
+    ) {
+        let min_sizes = vec![Size::new_checked(min_width, min_height); x.rects.len()];
+        prop_assert!((0.0..=1.0).contains(&KeepReadable::new(min_sizes).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn keep_readable_returns_1_for_worst_case() {
+        let min_sizes = vec![Size::new_checked(640, 384), Size::new_checked(640, 384)];
+        let rects = [Rect::new_checked(0, 0, 1, 1), Rect::new_checked(0, 0, 1, 1)];
+        assert_eq!(KeepReadable::new(min_sizes).evaluate(&rects), 1.0)
+    }
+
+    #[test]
+    fn keep_readable_returns_0_for_best_case() {
+        let min_sizes = vec![Size::new_checked(640, 384), Size::new_checked(1, 1)];
+        let rects = [
+            Rect::new_checked(0, 0, 640, 400),
+            Rect::new_checked(640, 0, 1, 1),
+        ];
+        assert_eq!(KeepReadable::new(min_sizes).evaluate(&rects), 0.0)
+    }
+}
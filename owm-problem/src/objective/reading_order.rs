@@ -1,14 +1,125 @@
+use std::{fmt, str::FromStr};
+
 use itertools::Itertools;
 
 use crate::Rect;
 
 pub struct PlaceInReadingOrder {
+    order: ReadingOrder,
     worst_case: f64,
 }
 
+/// How windows are read,
+/// like text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadingOrder {
+    pub direction: ReadingDirection,
+    pub major: ReadingMajor,
+}
+
+/// Horizontal direction windows are read in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadingDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+/// Whether rows or columns are read first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadingMajor {
+    /// Fill a row,
+    /// then the next row down,
+    /// moving back only to start it.
+    #[default]
+    Row,
+    /// Fill a column,
+    /// then the next column ahead,
+    /// moving up only to start it.
+    Column,
+}
+
+/// Error returned when failing to parse 'ReadingDirection'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'ltr' or 'rtl'")]
+pub struct ReadingDirectionFromStrError(String);
+
+/// Error returned when failing to parse 'ReadingMajor'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'row' or 'column'")]
+pub struct ReadingMajorFromStrError(String);
+
+impl FromStr for ReadingDirection {
+    type Err = ReadingDirectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ltr" => Ok(Self::LeftToRight),
+            "rtl" => Ok(Self::RightToLeft),
+            _ => Err(ReadingDirectionFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ReadingDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeftToRight => write!(f, "ltr"),
+            Self::RightToLeft => write!(f, "rtl"),
+        }
+    }
+}
+
+impl FromStr for ReadingMajor {
+    type Err = ReadingMajorFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "row" => Ok(Self::Row),
+            "column" => Ok(Self::Column),
+            _ => Err(ReadingMajorFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ReadingMajor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row => write!(f, "row"),
+            Self::Column => write!(f, "column"),
+        }
+    }
+}
+
+impl ReadingOrder {
+    /// Return whether `next` should be read
+    /// before `rect`.
+    fn is_out_of_order(self, rect: &Rect, next: &Rect) -> bool {
+        let (next_is_behind, next_is_ahead) = match self.direction {
+            ReadingDirection::LeftToRight => {
+                (next.left() < rect.left(), next.left() >= rect.right())
+            }
+            ReadingDirection::RightToLeft => {
+                (next.right() > rect.right(), next.right() <= rect.left())
+            }
+        };
+        match self.major {
+            // Moving back is fine
+            // when starting a new row.
+            ReadingMajor::Row => {
+                next.top() < rect.top() || (next_is_behind && next.top() < rect.bottom())
+            }
+            // Moving up is fine
+            // when starting a new column.
+            ReadingMajor::Column => next_is_behind || (next.top() < rect.top() && !next_is_ahead),
+        }
+    }
+}
+
 impl PlaceInReadingOrder {
-    pub fn new(count: usize) -> Self {
+    pub fn new(order: ReadingOrder, count: usize) -> Self {
         Self {
+            order,
             worst_case: count.saturating_sub(1) as f64,
         }
     }
@@ -20,7 +131,7 @@ impl PlaceInReadingOrder {
             rects
                 .iter()
                 .tuple_windows()
-                .filter(|(rect, other)| other.top() < rect.top() || other.left() < rect.left())
+                .filter(|(rect, other)| self.order.is_out_of_order(rect, other))
                 .count() as f64
                 / self.worst_case
         }
@@ -37,10 +148,12 @@ mod tests {
     use super::*;
 
     #[proptest]
-    fn place_in_reading_order_returns_values_in_range_0_1(x: ContainedRects) {
-        prop_assert!(
-            (0.0..=1.0).contains(&PlaceInReadingOrder::new(x.rects.len()).evaluate(&x.rects))
-        )
+    fn place_in_reading_order_returns_values_in_range_0_1(
+        #[strategy(arbitrary_reading_order())] order: ReadingOrder,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0)
+            .contains(&PlaceInReadingOrder::new(order, x.rects.len()).evaluate(&x.rects)))
     }
 
     #[test]
@@ -50,13 +163,19 @@ mod tests {
             Rect::new_checked(1, 0, 1, 1),
             Rect::new_checked(0, 0, 1, 1),
         ];
-        assert_eq!(PlaceInReadingOrder::new(rects.len()).evaluate(&rects), 1.0);
+        assert_eq!(
+            PlaceInReadingOrder::new(ReadingOrder::default(), rects.len()).evaluate(&rects),
+            1.0
+        );
         let rects = [
             Rect::new_checked(0, 2, 1, 1),
             Rect::new_checked(0, 1, 1, 1),
             Rect::new_checked(0, 0, 1, 1),
         ];
-        assert_eq!(PlaceInReadingOrder::new(rects.len()).evaluate(&rects), 1.0);
+        assert_eq!(
+            PlaceInReadingOrder::new(ReadingOrder::default(), rects.len()).evaluate(&rects),
+            1.0
+        );
     }
 
     #[test]
@@ -66,12 +185,149 @@ mod tests {
             Rect::new_checked(1, 0, 1, 1),
             Rect::new_checked(2, 0, 1, 1),
         ];
-        assert_eq!(PlaceInReadingOrder::new(rects.len()).evaluate(&rects), 0.0);
+        assert_eq!(
+            PlaceInReadingOrder::new(ReadingOrder::default(), rects.len()).evaluate(&rects),
+            0.0
+        );
         let rects = [
             Rect::new_checked(0, 0, 1, 1),
             Rect::new_checked(0, 1, 1, 1),
             Rect::new_checked(0, 2, 1, 1),
         ];
-        assert_eq!(PlaceInReadingOrder::new(rects.len()).evaluate(&rects), 0.0);
+        assert_eq!(
+            PlaceInReadingOrder::new(ReadingOrder::default(), rects.len()).evaluate(&rects),
+            0.0
+        );
+    }
+
+    #[test]
+    fn place_in_reading_order_follows_direction() {
+        let ltr = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(1, 0, 1, 1),
+            Rect::new_checked(2, 0, 1, 1),
+        ];
+        let rtl = [
+            Rect::new_checked(2, 0, 1, 1),
+            Rect::new_checked(1, 0, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+        ];
+        for major in [ReadingMajor::Row, ReadingMajor::Column] {
+            let order = ReadingOrder {
+                direction: ReadingDirection::RightToLeft,
+                major,
+            };
+            assert_eq!(PlaceInReadingOrder::new(order, 3).evaluate(&rtl), 0.0);
+            assert_eq!(PlaceInReadingOrder::new(order, 3).evaluate(&ltr), 1.0);
+        }
+    }
+
+    #[test]
+    fn place_in_reading_order_follows_major() {
+        // ```
+        // 02
+        // 13
+        // ```
+        let columns = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 1, 1, 1),
+            Rect::new_checked(1, 0, 1, 1),
+            Rect::new_checked(1, 1, 1, 1),
+        ];
+        // ```
+        // 20
+        // 31
+        // ```
+        let rtl_columns = [
+            Rect::new_checked(1, 0, 1, 1),
+            Rect::new_checked(1, 1, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 1, 1, 1),
+        ];
+        for (direction, columns) in [
+            (ReadingDirection::LeftToRight, columns),
+            (ReadingDirection::RightToLeft, rtl_columns),
+        ] {
+            let row_major = ReadingOrder {
+                direction,
+                major: ReadingMajor::Row,
+            };
+            let column_major = ReadingOrder {
+                direction,
+                major: ReadingMajor::Column,
+            };
+            assert_eq!(
+                PlaceInReadingOrder::new(column_major, 4).evaluate(&columns),
+                0.0
+            );
+            assert!(PlaceInReadingOrder::new(row_major, 4).evaluate(&columns) > 0.0);
+        }
+    }
+
+    #[test]
+    fn place_in_reading_order_row_major_wraps_to_next_row() {
+        // ```
+        // 01
+        // 23
+        // ```
+        let rows = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(1, 0, 1, 1),
+            Rect::new_checked(0, 1, 1, 1),
+            Rect::new_checked(1, 1, 1, 1),
+        ];
+        let row_major = ReadingOrder {
+            direction: ReadingDirection::LeftToRight,
+            major: ReadingMajor::Row,
+        };
+        let column_major = ReadingOrder {
+            direction: ReadingDirection::LeftToRight,
+            major: ReadingMajor::Column,
+        };
+        assert_eq!(PlaceInReadingOrder::new(row_major, 4).evaluate(&rows), 0.0);
+        assert!(PlaceInReadingOrder::new(column_major, 4).evaluate(&rows) > 0.0);
+    }
+
+    #[test]
+    fn place_in_reading_order_row_major_penalizes_moving_up() {
+        let rects = [Rect::new_checked(0, 1, 1, 1), Rect::new_checked(1, 0, 1, 1)];
+        assert_eq!(
+            PlaceInReadingOrder::new(ReadingOrder::default(), rects.len()).evaluate(&rects),
+            1.0
+        );
+    }
+
+    #[test]
+    fn place_in_reading_order_column_major_penalizes_moving_back() {
+        let rects = [Rect::new_checked(1, 0, 1, 1), Rect::new_checked(0, 1, 1, 1)];
+        let order = ReadingOrder {
+            direction: ReadingDirection::LeftToRight,
+            major: ReadingMajor::Column,
+        };
+        assert_eq!(
+            PlaceInReadingOrder::new(order, rects.len()).evaluate(&rects),
+            1.0
+        );
+    }
+
+    #[test]
+    fn reading_order_round_trips_through_strings() {
+        for direction in [ReadingDirection::LeftToRight, ReadingDirection::RightToLeft] {
+            assert_eq!(direction.to_string().parse(), Ok(direction))
+        }
+        for major in [ReadingMajor::Row, ReadingMajor::Column] {
+            assert_eq!(major.to_string().parse(), Ok(major))
+        }
+    }
+
+    fn arbitrary_reading_order() -> impl Strategy<Value = ReadingOrder> {
+        (
+            prop_oneof![
+                Just(ReadingDirection::LeftToRight),
+                Just(ReadingDirection::RightToLeft),
+            ],
+            prop_oneof![Just(ReadingMajor::Row), Just(ReadingMajor::Column)],
+        )
+            .prop_map(|(direction, major)| ReadingOrder { direction, major })
     }
 }
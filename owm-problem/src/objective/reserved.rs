@@ -0,0 +1,103 @@
+use itertools::Itertools;
+
+use crate::rect::{covered_area, Rect};
+
+pub struct AvoidReserved {
+    reserved: Vec<Rect>,
+    worst_case: f64,
+}
+
+impl AvoidReserved {
+    pub fn new(reserved: Vec<Rect>, count: usize) -> Self {
+        Self {
+            // A window can cover at most all reserved area.
+            worst_case: (count * covered_area(&reserved)) as f64,
+            reserved,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            rects
+                .iter()
+                .map(|rect| {
+                    covered_area(
+                        &self
+                            .reserved
+                            .iter()
+                            .filter_map(|reserved| rect.overlap(reserved))
+                            .collect_vec(),
+                    )
+                })
+                .sum::<usize>() as f64
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::repeat_n;
+
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::{ContainedRects, ContainedRectsParams};
+
+    use super::*;
+
+    #[proptest]
+    fn avoid_reserved_returns_values_in_range_0_1(
+        #[strategy(arbitrary_avoid_reserved_args())] args: (ContainedRects, Vec<Rect>),
+    ) {
+        prop_assert!((0.0..=1.0)
+            .contains(&AvoidReserved::new(args.1, args.0.rects.len()).evaluate(&args.0.rects)))
+    }
+
+    #[proptest]
+    fn avoid_reserved_returns_1_for_worst_case(
+        #[strategy(arbitrary_avoid_reserved_args())] args: (ContainedRects, Vec<Rect>),
+    ) {
+        prop_assume!(!args.1.is_empty());
+        let rects = repeat_n(
+            Rect::new(0, 0, args.0.container.width, args.0.container.height),
+            args.0.rects.len().max(1),
+        )
+        .collect_vec();
+        prop_assert_eq!(
+            AvoidReserved::new(args.1, rects.len()).evaluate(&rects),
+            1.0
+        )
+    }
+
+    #[test]
+    fn avoid_reserved_returns_0_for_best_case() {
+        let reserved = vec![Rect::new_checked(5, 5, 5, 5)];
+        let rects = [
+            Rect::new_checked(0, 0, 10, 5),
+            Rect::new_checked(0, 5, 5, 5),
+        ];
+        assert_eq!(
+            AvoidReserved::new(reserved, rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    fn arbitrary_avoid_reserved_args() -> BoxedStrategy<(ContainedRects, Vec<Rect>)> {
+        ContainedRects::arbitrary()
+            .prop_flat_map(|x| {
+                (
+                    ContainedRects::arbitrary_with(ContainedRectsParams {
+                        width_range: x.container.width..=x.container.width,
+                        height_range: x.container.height..=x.container.height,
+                        len_range: 0..=4,
+                    }),
+                    Just(x),
+                )
+            })
+            .prop_map(|(reserved, x)| (x, reserved.rects))
+            .boxed()
+    }
+}
@@ -0,0 +1,92 @@
+use itertools::Itertools;
+
+use crate::{post_processing::div_ceil, Rect, Size};
+
+/// Keep each window no larger than the one before it.
+pub struct KeepSizesOrdered {
+    worst_case: f64,
+}
+
+impl KeepSizesOrdered {
+    pub fn new(max_size: Size, count: usize) -> Self {
+        Self {
+            // Growth telescopes,
+            // so the most total growth
+            // comes from alternating min and max area.
+            worst_case: (div_ceil(count.saturating_sub(1), 2) * (max_size.area().get() - 1)) as f64,
+        }
+    }
+
+    /// Return how much area windows gain
+    /// over the window before them.
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            rects
+                .iter()
+                .tuple_windows()
+                .map(|(rect, next)| next.area().get().saturating_sub(rect.area().get()))
+                .sum::<usize>() as f64
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn keep_sizes_ordered_returns_values_in_range_0_1(x: ContainedRects) {
+        prop_assert!((0.0..=1.0)
+            .contains(&KeepSizesOrdered::new(x.container, x.rects.len()).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn keep_sizes_ordered_returns_1_for_worst_case() {
+        let max_size = Size::new_checked(2, 2);
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 2, 2),
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 2, 2),
+        ];
+        assert_eq!(
+            KeepSizesOrdered::new(max_size, rects.len()).evaluate(&rects),
+            1.0
+        )
+    }
+
+    #[test]
+    fn keep_sizes_ordered_returns_0_for_best_case() {
+        let max_size = Size::new_checked(2, 2);
+        let rects = [
+            Rect::new_checked(0, 0, 2, 2),
+            Rect::new_checked(0, 0, 2, 1),
+            Rect::new_checked(0, 0, 1, 2),
+        ];
+        assert_eq!(
+            KeepSizesOrdered::new(max_size, rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn keep_sizes_ordered_scales_with_growth() {
+        let max_size = Size::new_checked(10, 10);
+        let slightly_larger = [Rect::new_checked(0, 0, 5, 5), Rect::new_checked(0, 0, 5, 6)];
+        let much_larger = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(0, 0, 10, 10),
+        ];
+        let objective = KeepSizesOrdered::new(max_size, 2);
+        assert!(objective.evaluate(&slightly_larger) > 0.0);
+        assert!(objective.evaluate(&slightly_larger) < objective.evaluate(&much_larger));
+    }
+}
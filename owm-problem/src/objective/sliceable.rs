@@ -0,0 +1,129 @@
+use itertools::Itertools;
+
+use crate::Rect;
+
+/// Prefer layouts that can be made
+/// by recursively cutting the container
+/// horizontally and vertically,
+/// like a tiling window manager.
+pub struct PreferSliceable {
+    worst_case: f64,
+}
+
+impl PreferSliceable {
+    pub fn new(count: usize) -> Self {
+        Self {
+            worst_case: count.saturating_sub(1) as f64,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if rects.len() < 2 {
+            0.0
+        } else {
+            unsliceable(rects.to_vec()) as f64 / self.worst_case
+        }
+    }
+}
+
+/// Return how many windows beyond the first
+/// are in blocks no straight cut can divide.
+fn unsliceable(rects: Vec<Rect>) -> usize {
+    if rects.len() < 2 {
+        return 0;
+    }
+    for (start, end) in [
+        (
+            Rect::left as fn(&Rect) -> usize,
+            Rect::right as fn(&Rect) -> usize,
+        ),
+        (Rect::top, Rect::bottom),
+    ] {
+        let groups = split(&rects, start, end);
+        if groups.len() > 1 {
+            return groups.into_iter().map(unsliceable).sum();
+        }
+    }
+    rects.len() - 1
+}
+
+/// Split `rects` at every cut
+/// perpendicular to an axis
+/// that no rectangle crosses.
+fn split(rects: &[Rect], start: fn(&Rect) -> usize, end: fn(&Rect) -> usize) -> Vec<Vec<Rect>> {
+    let mut groups: Vec<Vec<Rect>> = Vec::new();
+    let mut group_end = 0;
+    for rect in rects.iter().sorted_by_key(|rect| start(rect)) {
+        match groups.last_mut() {
+            Some(group) if start(rect) < group_end => group.push(*rect),
+            _ => groups.push(vec![*rect]),
+        }
+        group_end = group_end.max(end(rect));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn prefer_sliceable_returns_values_in_range_0_1(x: ContainedRects) {
+        prop_assert!((0.0..=1.0).contains(&PreferSliceable::new(x.rects.len()).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn prefer_sliceable_returns_1_for_worst_case() {
+        // ```
+        // 00011
+        // 00011
+        // 32241
+        // 33444
+        // 33444
+        // ```
+        let rects = [
+            Rect::new_checked(0, 0, 3, 2),
+            Rect::new_checked(3, 0, 2, 3),
+            Rect::new_checked(1, 2, 2, 1),
+            Rect::new_checked(0, 2, 1, 3),
+            Rect::new_checked(2, 3, 3, 2),
+        ];
+        assert_eq!(PreferSliceable::new(rects.len()).evaluate(&rects), 1.0)
+    }
+
+    #[test]
+    fn prefer_sliceable_returns_0_for_best_case() {
+        // ```
+        // 0011
+        // 0011
+        // 0023
+        // 0023
+        // ```
+        let rects = [
+            Rect::new_checked(0, 0, 2, 4),
+            Rect::new_checked(2, 0, 2, 2),
+            Rect::new_checked(2, 2, 1, 2),
+            Rect::new_checked(3, 2, 1, 2),
+        ];
+        assert_eq!(PreferSliceable::new(rects.len()).evaluate(&rects), 0.0)
+    }
+
+    #[test]
+    fn prefer_sliceable_penalizes_only_unsliceable_blocks() {
+        // A pinwheel beside a full-height window.
+        let rects = [
+            Rect::new_checked(0, 0, 3, 2),
+            Rect::new_checked(3, 0, 2, 3),
+            Rect::new_checked(1, 2, 2, 1),
+            Rect::new_checked(0, 2, 1, 3),
+            Rect::new_checked(2, 3, 3, 2),
+            Rect::new_checked(5, 0, 5, 5),
+        ];
+        assert_eq!(PreferSliceable::new(rects.len()).evaluate(&rects), 0.8)
+    }
+}
@@ -0,0 +1,219 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Rect, Size};
+
+pub struct MaximizeSymmetry {
+    axes: SymmetryAxes,
+    container: Size,
+    worst_case: f64,
+}
+
+/// Axes a layout should be mirror-symmetric across.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymmetryAxes {
+    /// Left half mirrors right half.
+    Horizontal,
+    /// Top half mirrors bottom half.
+    Vertical,
+    /// Both of the above.
+    Both,
+}
+
+/// Error returned when failing to parse 'SymmetryAxes'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("'{0}' is not one of 'horizontal', 'vertical', or 'both'")]
+pub struct SymmetryAxesFromStrError(String);
+
+impl FromStr for SymmetryAxes {
+    type Err = SymmetryAxesFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "horizontal" => Ok(Self::Horizontal),
+            "vertical" => Ok(Self::Vertical),
+            "both" => Ok(Self::Both),
+            _ => Err(SymmetryAxesFromStrError(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for SymmetryAxes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Horizontal => write!(f, "horizontal"),
+            Self::Vertical => write!(f, "vertical"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl SymmetryAxes {
+    fn horizontal(self) -> bool {
+        matches!(self, Self::Horizontal | Self::Both)
+    }
+
+    fn vertical(self) -> bool {
+        matches!(self, Self::Vertical | Self::Both)
+    }
+}
+
+impl MaximizeSymmetry {
+    pub fn new(axes: SymmetryAxes, container: Size, count: usize) -> Self {
+        // This assumes rectangles cannot exceed container bounds.
+        // Each edge of a rectangle is never farther
+        // than `container - 1`
+        // from the same edge of its own reflection.
+        let worst_case_per_rect = 2
+            * ((if axes.horizontal() {
+                container.width.get() - 1
+            } else {
+                0
+            }) + (if axes.vertical() {
+                container.height.get() - 1
+            } else {
+                0
+            }));
+        Self {
+            axes,
+            container,
+            worst_case: (count * worst_case_per_rect) as f64,
+        }
+    }
+
+    pub fn evaluate(&self, rects: &[Rect]) -> f64 {
+        if self.worst_case == 0.0 {
+            0.0
+        } else {
+            rects
+                .iter()
+                .map(|rect| {
+                    (if self.axes.horizontal() {
+                        let mirrored = rect.mirror_horizontally(self.container);
+                        rects
+                            .iter()
+                            .map(|other| mirrored.edge_dist(*other))
+                            .min()
+                            .unwrap()
+                    } else {
+                        0
+                    }) + (if self.axes.vertical() {
+                        let mirrored = rect.mirror_vertically(self.container);
+                        rects
+                            .iter()
+                            .map(|other| mirrored.edge_dist(*other))
+                            .min()
+                            .unwrap()
+                    } else {
+                        0
+                    })
+                })
+                .sum::<usize>() as f64
+                / self.worst_case
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::testing::ContainedRects;
+
+    use super::*;
+
+    #[proptest]
+    fn maximize_symmetry_returns_values_in_range_0_1(
+        #[strategy(arbitrary_symmetry_axes())] axes: SymmetryAxes,
+        x: ContainedRects,
+    ) {
+        prop_assert!((0.0..=1.0)
+            .contains(&MaximizeSymmetry::new(axes, x.container, x.rects.len()).evaluate(&x.rects)))
+    }
+
+    #[test]
+    fn maximize_symmetry_returns_1_for_worst_case() {
+        let container = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+            Rect::new_checked(0, 0, 1, 1),
+        ];
+        for axes in [
+            SymmetryAxes::Horizontal,
+            SymmetryAxes::Vertical,
+            SymmetryAxes::Both,
+        ] {
+            assert_eq!(
+                MaximizeSymmetry::new(axes, container, rects.len()).evaluate(&rects),
+                1.0
+            )
+        }
+    }
+
+    #[test]
+    fn maximize_symmetry_returns_0_for_best_case() {
+        let container = Size::new_checked(10, 10);
+        let rects = [
+            Rect::new_checked(0, 0, 10, 5),
+            Rect::new_checked(0, 5, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+        ];
+        assert_eq!(
+            MaximizeSymmetry::new(SymmetryAxes::Horizontal, container, rects.len())
+                .evaluate(&rects),
+            0.0
+        );
+        let rects = [
+            Rect::new_checked(0, 0, 5, 5),
+            Rect::new_checked(5, 0, 5, 5),
+            Rect::new_checked(0, 5, 5, 5),
+            Rect::new_checked(5, 5, 5, 5),
+        ];
+        assert_eq!(
+            MaximizeSymmetry::new(SymmetryAxes::Both, container, rects.len()).evaluate(&rects),
+            0.0
+        );
+    }
+
+    #[proptest]
+    fn maximize_symmetry_returns_0_for_rects_and_their_reflections(x: ContainedRects) {
+        let rects = x
+            .rects
+            .iter()
+            .flat_map(|rect| {
+                let mirrored = rect.mirror_horizontally(x.container);
+                [
+                    *rect,
+                    mirrored,
+                    rect.mirror_vertically(x.container),
+                    mirrored.mirror_vertically(x.container),
+                ]
+            })
+            .collect_vec();
+        prop_assert_eq!(
+            MaximizeSymmetry::new(SymmetryAxes::Both, x.container, rects.len()).evaluate(&rects),
+            0.0
+        )
+    }
+
+    #[test]
+    fn symmetry_axes_round_trips_through_strings() {
+        for axes in [
+            SymmetryAxes::Horizontal,
+            SymmetryAxes::Vertical,
+            SymmetryAxes::Both,
+        ] {
+            assert_eq!(axes.to_string().parse::<SymmetryAxes>(), Ok(axes))
+        }
+    }
+
+    fn arbitrary_symmetry_axes() -> impl Strategy<Value = SymmetryAxes> {
+        prop_oneof![
+            Just(SymmetryAxes::Horizontal),
+            Just(SymmetryAxes::Vertical),
+            Just(SymmetryAxes::Both),
+        ]
+    }
+}
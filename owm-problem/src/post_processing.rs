@@ -15,9 +15,33 @@ pub fn trim_outside(container: Size, rects: &mut [Rect]) {
     }
 }
 
-pub fn remove_gaps(max_size: Size, container: Size, rects: &mut [Rect]) {
-    debug_assert!(max_size.width <= container.width);
-    debug_assert!(max_size.height <= container.height);
+/// Expand rectangles to fill gaps,
+/// without growing the `i`th rectangle
+/// beyond `max_sizes[i]`.
+/// Rectangles grow up to `obstacles`,
+/// but not into them.
+pub fn remove_gaps(max_sizes: &[Size], obstacles: &[Rect], container: Size, rects: &mut [Rect]) {
+    debug_assert_eq!(max_sizes.len(), rects.len());
+    if obstacles.is_empty() {
+        _remove_gaps(max_sizes, container, rects)
+    } else {
+        // Obstacles cannot grow
+        // because their max size is their size.
+        let mut all_rects = rects.iter().chain(obstacles).copied().collect_vec();
+        let all_max_sizes = max_sizes
+            .iter()
+            .copied()
+            .chain(obstacles.iter().map(|obstacle| obstacle.size))
+            .collect_vec();
+        _remove_gaps(&all_max_sizes, container, &mut all_rects);
+        rects.copy_from_slice(&all_rects[..rects.len()]);
+    }
+}
+
+fn _remove_gaps(max_sizes: &[Size], container: Size, rects: &mut [Rect]) {
+    debug_assert!(max_sizes
+        .iter()
+        .all(|max_size| max_size.width <= container.width && max_size.height <= container.height));
 
     let flip_flop = |dist, x: usize, y: usize| {
         let x_ = x.min(div_ceil(dist, 2));
@@ -45,7 +69,8 @@ pub fn remove_gaps(max_size: Size, container: Size, rects: &mut [Rect]) {
         let x_rays = rects
             .iter()
             .zip(freedoms.iter())
-            .map(|(rect, freedoms)| {
+            .zip(max_sizes)
+            .map(|((rect, freedoms), max_size)| {
                 let y_range = rect.y_range_exclusive();
                 let max_free = max_size.width.get().saturating_sub(rect.width().get());
                 let left = if freedoms.left == 0 {
@@ -82,7 +107,8 @@ pub fn remove_gaps(max_size: Size, container: Size, rects: &mut [Rect]) {
         let y_rays = rects
             .iter()
             .zip(freedoms.iter())
-            .map(|(rect, freedoms)| {
+            .zip(max_sizes)
+            .map(|((rect, freedoms), max_size)| {
                 let x_range = rect.x_range_exclusive();
                 let max_free = max_size.height.get().saturating_sub(rect.height().get());
                 let top = if freedoms.top == 0 {
@@ -117,7 +143,7 @@ pub fn remove_gaps(max_size: Size, container: Size, rects: &mut [Rect]) {
             })
             .collect_vec();
 
-        for (rect, freedoms) in rects.iter().zip(freedoms.iter_mut()) {
+        for ((rect, freedoms), max_size) in rects.iter().zip(freedoms.iter_mut()).zip(max_sizes) {
             let (left, right) = flip_flop(
                 max_size.width.get().saturating_sub(rect.width().get()),
                 rect.left(),
@@ -397,6 +423,110 @@ pub fn overlap_borders(border_thickness: usize, container: Size, rects: &mut [Re
     }
 }
 
+/// Space to leave around windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Gaps {
+    /// Space between windows.
+    pub inner: usize,
+    /// Space between windows
+    /// and edges of the container.
+    pub outer: usize,
+    /// Leave no space
+    /// around a single window.
+    pub smart: bool,
+}
+
+/// Shrink rectangles
+/// so they are at least `gaps.inner` apart
+/// and at least `gaps.outer` from edges of `container`.
+/// Rectangles sharing a gap
+/// each give about half,
+/// favoring the first to give more.
+/// Overlapping rectangles
+/// are not separated.
+///
+/// Gaps are meant for the end of post-processing,
+/// after gaps found by optimization are removed,
+/// so objectives never see them.
+pub fn add_gaps(gaps: Gaps, container: Size, rects: &mut [Rect]) {
+    if gaps.smart && rects.len() == 1 {
+        return;
+    }
+
+    let share = |i: usize, other_i: usize, len: usize| {
+        if i < other_i {
+            div_ceil(len, 2)
+        } else {
+            len / 2
+        }
+    };
+
+    let shrinks = rects
+        .iter()
+        .enumerate()
+        .map(|(i, rect)| {
+            let mut shrinks = Sides {
+                left: gaps.outer.saturating_sub(rect.left()),
+                right: gaps
+                    .outer
+                    .saturating_sub(container.width.get().saturating_sub(rect.right())),
+                top: gaps.outer.saturating_sub(rect.top()),
+                bottom: gaps
+                    .outer
+                    .saturating_sub(container.height.get().saturating_sub(rect.bottom())),
+            };
+            for (other_i, other_rect) in rects.iter().enumerate().filter(|(j, _)| *j != i) {
+                if rect
+                    .y_range_exclusive()
+                    .intersects(other_rect.y_range_exclusive())
+                {
+                    if other_rect.right() <= rect.left() {
+                        shrinks.left = shrinks.left.max(share(
+                            i,
+                            other_i,
+                            gaps.inner.saturating_sub(rect.left() - other_rect.right()),
+                        ));
+                    }
+                    if rect.right() <= other_rect.left() {
+                        shrinks.right = shrinks.right.max(share(
+                            i,
+                            other_i,
+                            gaps.inner.saturating_sub(other_rect.left() - rect.right()),
+                        ));
+                    }
+                }
+                if rect
+                    .x_range_exclusive()
+                    .intersects(other_rect.x_range_exclusive())
+                {
+                    if other_rect.bottom() <= rect.top() {
+                        shrinks.top = shrinks.top.max(share(
+                            i,
+                            other_i,
+                            gaps.inner.saturating_sub(rect.top() - other_rect.bottom()),
+                        ));
+                    }
+                    if rect.bottom() <= other_rect.top() {
+                        shrinks.bottom = shrinks.bottom.max(share(
+                            i,
+                            other_i,
+                            gaps.inner.saturating_sub(other_rect.top() - rect.bottom()),
+                        ));
+                    }
+                }
+            }
+            shrinks
+        })
+        .collect_vec();
+
+    for (rect, shrinks) in rects.iter_mut().zip(shrinks) {
+        rect.shrink_left(shrinks.left);
+        rect.shrink_right(shrinks.right);
+        rect.shrink_top(shrinks.top);
+        rect.shrink_bottom(shrinks.bottom);
+    }
+}
+
 type Freedoms = Sides<usize>;
 
 #[derive(Clone, Copy, Debug)]
@@ -413,7 +543,7 @@ impl<T> Sides<T> {
     }
 }
 
-fn div_ceil(x: usize, y: usize) -> usize {
+pub(crate) fn div_ceil(x: usize, y: usize) -> usize {
     if x % y > 0 {
         x / y + 1
     } else {
@@ -441,7 +571,7 @@ mod tests {
             Rect::new_checked(2, 7, 1, 1),
             Rect::new_checked(7, 7, 1, 1),
         ];
-        remove_gaps(container, container, &mut rects);
+        remove_gaps(&[container; 3], &[], container, &mut rects);
         assert_eq!(
             rects,
             [
@@ -452,6 +582,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn remove_gaps_expands_up_to_obstacles() {
+        let container = Size::new_checked(10, 10);
+        let obstacles = [Rect::new_checked(0, 8, 10, 2)];
+        let mut rects = [Rect::new_checked(0, 0, 1, 1), Rect::new_checked(7, 0, 1, 1)];
+        remove_gaps(&[container; 2], &obstacles, container, &mut rects);
+        for rect in rects {
+            assert_eq!(rect.overlap(&obstacles[0]), None);
+        }
+        assert_eq!(
+            covered_area(&rects.iter().chain(&obstacles).copied().collect_vec()),
+            container.area().get()
+        );
+    }
+
     #[ignore = "fails when corners touch or rectangles overlap"]
     // Four or more rectangles can be in an arrangement
     // requiring overlapping
@@ -468,7 +613,12 @@ mod tests {
         args: ContainedRects,
     ) {
         let mut rects = args.rects;
-        remove_gaps(args.container, args.container, &mut rects);
+        remove_gaps(
+            &vec![args.container; rects.len()],
+            &[],
+            args.container,
+            &mut rects,
+        );
         prop_assert_eq!(covered_area(&rects), args.container.area().get())
     }
 
@@ -481,7 +631,12 @@ mod tests {
     ) {
         prop_assume!(obscured_area(&args.rects) == 0);
         let mut rects = args.rects;
-        remove_gaps(args.container, args.container, &mut rects);
+        remove_gaps(
+            &vec![args.container; rects.len()],
+            &[],
+            args.container,
+            &mut rects,
+        );
         prop_assert_eq!(
             rects.into_iter().map(|x| x.area().get()).sum::<usize>(),
             args.container.area().get()
@@ -493,17 +648,17 @@ mod tests {
     fn remove_gaps_does_not_make_rects_overlap_if_they_did_not_already(args: RemoveGapsArgs) {
         prop_assume!(obscured_area(&args.rects) == 0);
         let mut rects = args.rects;
-        remove_gaps(args.max_size, args.container, &mut rects);
+        remove_gaps(&args.max_sizes, &[], args.container, &mut rects);
         prop_assert_eq!(obscured_area(&rects), 0)
     }
 
     #[proptest]
     fn remove_gaps_respects_max_size(args: RemoveGapsArgs) {
         let mut rects = args.rects;
-        remove_gaps(args.max_size, args.container, &mut rects);
-        for rect in rects {
-            prop_assert!(rect.width() <= args.max_size.width);
-            prop_assert!(rect.height() <= args.max_size.height);
+        remove_gaps(&args.max_sizes, &[], args.container, &mut rects);
+        for (rect, max_size) in rects.into_iter().zip(args.max_sizes) {
+            prop_assert!(rect.width() <= max_size.width);
+            prop_assert!(rect.height() <= max_size.height);
         }
     }
 
@@ -556,6 +711,61 @@ mod tests {
         )
     }
 
+    #[test]
+    fn add_gaps_spaces_rects_from_each_other_and_container() {
+        let container = Size::new_checked(10, 10);
+        let mut rects = [
+            Rect::new_checked(0, 0, 5, 10),
+            Rect::new_checked(5, 0, 5, 10),
+        ];
+        add_gaps(
+            Gaps {
+                inner: 3,
+                outer: 1,
+                smart: false,
+            },
+            container,
+            &mut rects,
+        );
+        assert_eq!(
+            rects,
+            [Rect::new_checked(1, 1, 2, 8), Rect::new_checked(6, 1, 3, 8)]
+        )
+    }
+
+    #[test]
+    fn add_gaps_with_smart_gaps_leaves_single_rect() {
+        let container = Size::new_checked(10, 10);
+        let mut rects = [Rect::new_checked(0, 0, 10, 10)];
+        add_gaps(
+            Gaps {
+                inner: 3,
+                outer: 1,
+                smart: true,
+            },
+            container,
+            &mut rects,
+        );
+        assert_eq!(rects, [Rect::new_checked(0, 0, 10, 10)])
+    }
+
+    #[proptest]
+    fn add_gaps_only_shrinks_rects(x: ContainedRects, inner: u8, outer: u8, smart: bool) {
+        let mut rects = x.rects.clone();
+        add_gaps(
+            Gaps {
+                inner: inner.into(),
+                outer: outer.into(),
+                smart,
+            },
+            x.container,
+            &mut rects,
+        );
+        for (rect, original) in rects.into_iter().zip(x.rects) {
+            prop_assert_eq!(rect.overlap(&original), Some(rect));
+        }
+    }
+
     #[test]
     fn div_ceil_works_for_simple_cases() {
         assert_eq!(div_ceil(11, 2), 6);
@@ -571,7 +781,7 @@ mod tests {
 
     #[derive(Clone, Debug)]
     struct RemoveGapsArgs {
-        max_size: Size,
+        max_sizes: Vec<Size>,
         container: Size,
         rects: Vec<Rect>,
     }
@@ -584,18 +794,21 @@ mod tests {
             ContainedRects::arbitrary_with(range)
                 .prop_flat_map(|x| {
                     (
-                        (
-                            x.rects.iter().map(|x| x.width().get()).max().unwrap_or(1)
-                                ..=x.container.width.get(),
-                            x.rects.iter().map(|x| x.height().get()).max().unwrap_or(1)
-                                ..=x.container.height.get(),
-                        )
-                            .prop_map(|(width, height)| Size::new_checked(width, height)),
+                        x.rects
+                            .iter()
+                            .map(|rect| {
+                                (
+                                    rect.width().get()..=x.container.width.get(),
+                                    rect.height().get()..=x.container.height.get(),
+                                )
+                                    .prop_map(|(width, height)| Size::new_checked(width, height))
+                            })
+                            .collect_vec(),
                         Just(x),
                     )
                 })
-                .prop_map(|(max_size, x)| Self {
-                    max_size,
+                .prop_map(|(max_sizes, x)| Self {
+                    max_sizes,
                     container: x.container,
                     rects: x.rects,
                 })
@@ -133,6 +133,40 @@ impl Rect {
         self.size.height = unsafe { NonZeroUsize::new_unchecked(self.size.height.get() + value) };
     }
 
+    /// Shrink by `value`,
+    /// or as much as possible
+    /// while keeping at least one pixel.
+    pub fn shrink_left(&mut self, value: usize) {
+        let value = value.min(self.size.width.get() - 1);
+        self.pos.x += value;
+        self.size.width = unsafe { NonZeroUsize::new_unchecked(self.size.width.get() - value) };
+    }
+
+    /// Shrink by `value`,
+    /// or as much as possible
+    /// while keeping at least one pixel.
+    pub fn shrink_right(&mut self, value: usize) {
+        let value = value.min(self.size.width.get() - 1);
+        self.size.width = unsafe { NonZeroUsize::new_unchecked(self.size.width.get() - value) };
+    }
+
+    /// Shrink by `value`,
+    /// or as much as possible
+    /// while keeping at least one pixel.
+    pub fn shrink_top(&mut self, value: usize) {
+        let value = value.min(self.size.height.get() - 1);
+        self.pos.y += value;
+        self.size.height = unsafe { NonZeroUsize::new_unchecked(self.size.height.get() - value) };
+    }
+
+    /// Shrink by `value`,
+    /// or as much as possible
+    /// while keeping at least one pixel.
+    pub fn shrink_bottom(&mut self, value: usize) {
+        let value = value.min(self.size.height.get() - 1);
+        self.size.height = unsafe { NonZeroUsize::new_unchecked(self.size.height.get() - value) };
+    }
+
     pub fn x_range_exclusive(&self) -> RangeExclusive<usize> {
         RangeExclusive(self.left(), self.right())
     }
@@ -169,6 +203,35 @@ impl Rect {
     pub fn diff(self, other: Self) -> usize {
         self.pos.dist(other.pos) + self.size.diff(other.size)
     }
+
+    /// Return the total distance between corresponding edges.
+    /// Unlike `diff`,
+    /// this does not change
+    /// when both rectangles are mirrored.
+    pub fn edge_dist(self, other: Self) -> usize {
+        Pos::new(self.left(), self.top()).dist(Pos::new(other.left(), other.top()))
+            + Pos::new(self.right(), self.bottom()).dist(Pos::new(other.right(), other.bottom()))
+    }
+
+    /// Return this rectangle
+    /// reflected across the vertical center line of `container`.
+    /// This assumes the rectangle does not exceed container bounds.
+    pub fn mirror_horizontally(self, container: Size) -> Self {
+        Self {
+            pos: Pos::new(container.width.get() - self.right(), self.y()),
+            size: self.size,
+        }
+    }
+
+    /// Return this rectangle
+    /// reflected across the horizontal center line of `container`.
+    /// This assumes the rectangle does not exceed container bounds.
+    pub fn mirror_vertically(self, container: Size) -> Self {
+        Self {
+            pos: Pos::new(self.x(), container.height.get() - self.bottom()),
+            size: self.size,
+        }
+    }
 }
 
 impl Pos {
@@ -306,6 +369,14 @@ impl<T> RangeExclusive<T> {
     }
 }
 
+impl RangeExclusive<usize> {
+    /// Return the length of the part of this range
+    /// also in `other`.
+    pub fn intersection_len(self, other: RangeExclusive<usize>) -> usize {
+        self.1.min(other.1).saturating_sub(self.0.max(other.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -347,4 +418,32 @@ mod tests {
     ) {
         prop_assert_eq!(x.intersects(y), y.intersects(x));
     }
+
+    #[test]
+    fn range_exclusive_intersection_len_works_for_simple_cases() {
+        assert_eq!(
+            RangeExclusive(0, 2).intersection_len(RangeExclusive(1, 2)),
+            1
+        );
+        assert_eq!(
+            RangeExclusive(0, 3).intersection_len(RangeExclusive(1, 2)),
+            1
+        );
+        assert_eq!(
+            RangeExclusive(0, 4).intersection_len(RangeExclusive(2, 8)),
+            2
+        );
+        assert_eq!(
+            RangeExclusive(0, 1).intersection_len(RangeExclusive(1, 2)),
+            0
+        );
+    }
+
+    #[proptest]
+    fn range_exclusive_intersection_len_is_symmetrical(
+        x: RangeExclusive<usize>,
+        y: RangeExclusive<usize>,
+    ) {
+        prop_assert_eq!(x.intersection_len(y), y.intersection_len(x));
+    }
 }
@@ -0,0 +1,162 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::rect::{Pos, Rect, Size};
+
+/// A region of the container,
+/// in lengths that may depend on its size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    pub x: Length,
+    pub y: Length,
+    pub width: Length,
+    pub height: Length,
+}
+
+/// A length in pixels
+/// or as a percentage of the container.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    Pixels(usize),
+    Percent(f64),
+}
+
+/// Error returned when failing to parse 'Region'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+pub enum RegionFromStrError {
+    /// Value does not have four parts.
+    #[error("'{0}' is not of the form 'X,Y,WIDTH,HEIGHT'")]
+    WrongNumberOfParts(String),
+    /// A part is not a valid length.
+    #[error("{0}")]
+    Length(LengthFromStrError),
+}
+
+/// Error returned when failing to parse 'Length'.
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+pub enum LengthFromStrError {
+    /// Value is not a number of pixels.
+    #[error("{0}")]
+    Pixels(ParseIntError),
+    /// Value is not a percentage.
+    #[error("'{0}' is not a percentage in range 0..=100")]
+    Percent(String),
+}
+
+impl FromStr for Region {
+    type Err = RegionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split(',').collect_tuple() {
+            Some((x, y, width, height)) => Ok(Self {
+                x: x.parse().map_err(Self::Err::Length)?,
+                y: y.parse().map_err(Self::Err::Length)?,
+                width: width.parse().map_err(Self::Err::Length)?,
+                height: height.parse().map_err(Self::Err::Length)?,
+            }),
+            None => Err(Self::Err::WrongNumberOfParts(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{},{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl FromStr for Length {
+    type Err = LengthFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().strip_suffix('%') {
+            Some(percent) => match percent.trim().parse::<f64>() {
+                Ok(x) if (0.0..=100.0).contains(&x) => Ok(Self::Percent(x)),
+                _ => Err(Self::Err::Percent(s.to_owned())),
+            },
+            None => s
+                .trim()
+                .parse()
+                .map(Self::Pixels)
+                .map_err(Self::Err::Pixels),
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pixels(x) => write!(f, "{x}"),
+            Self::Percent(x) => write!(f, "{x}%"),
+        }
+    }
+}
+
+impl Length {
+    /// Return this length in pixels.
+    pub fn resolve(self, container_length: usize) -> usize {
+        match self {
+            Self::Pixels(x) => x,
+            Self::Percent(x) => (x / 100.0 * container_length as f64).round() as usize,
+        }
+    }
+}
+
+impl Region {
+    /// Return this region in pixels,
+    /// trimmed to `container`,
+    /// or `None` if no part of it is inside `container`.
+    pub fn resolve(&self, container: Size) -> Option<Rect> {
+        let x = self.x.resolve(container.width.get());
+        let y = self.y.resolve(container.height.get());
+        let width = self
+            .width
+            .resolve(container.width.get())
+            .min(container.width.get().saturating_sub(x));
+        let height = self
+            .height
+            .resolve(container.height.get())
+            .min(container.height.get().saturating_sub(y));
+        Some(Rect {
+            pos: Pos::new(x, y),
+            size: Size::new(width.try_into().ok()?, height.try_into().ok()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_resolves_pixels_and_percentages() {
+        let container = Size::new_checked(1000, 500);
+        assert_eq!(
+            "75%,50%,25%,50%"
+                .parse::<Region>()
+                .unwrap()
+                .resolve(container),
+            Some(Rect::new_checked(750, 250, 250, 250))
+        );
+        assert_eq!(
+            "900,400,320,180"
+                .parse::<Region>()
+                .unwrap()
+                .resolve(container),
+            Some(Rect::new_checked(900, 400, 100, 100))
+        );
+        assert_eq!(
+            "1000,0,10,10".parse::<Region>().unwrap().resolve(container),
+            None
+        );
+    }
+
+    #[test]
+    fn region_rejects_invalid_strings() {
+        assert!("0,0,10".parse::<Region>().is_err());
+        assert!("0,0,10,10,10".parse::<Region>().is_err());
+        assert!("0,0,101%,10".parse::<Region>().is_err());
+        assert!("0,-1,10,10".parse::<Region>().is_err());
+    }
+}
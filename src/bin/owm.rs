@@ -1,10 +1,17 @@
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use clap::Parser;
-use owm::{LayoutGen, Status};
-use owm_problem::{AreaRatio, AspectRatio, Size, Weight, Weights};
+use owm::{LayoutGen, LayoutGenParams, Status};
+use owm_problem::{
+    encoding::{BitCode, Encoding, Template},
+    post_processing::Gaps,
+    Adjacency, AreaRatio, AspectRatio, Constraint, GridCount, IndexGroup, Length, MainAnchor,
+    ReadingDirection, ReadingMajor, ReadingOrder, Region, RegionFromStrError, Size, SymmetryAxes,
+    VisibleFraction, Weight, Weights,
+};
 use wayland_client::protocol::wl_seat::WlSeat;
 use wayland_client::Connection;
 use wayland_client::{
@@ -35,23 +42,214 @@ struct Args {
     #[arg(long, value_name = "NAMESPACE", default_value = "owm")]
     namespace: String,
 
-    #[arg(long, value_name = "NON_ZERO_UINT", default_value_t = NonZeroUsize::new(320).unwrap())]
-    min_width: NonZeroUsize,
+    /// Minimum widths of windows.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    #[arg(
+        long,
+        value_name = "NON_ZERO_UINTS",
+        value_delimiter = ',',
+        default_value = "320"
+    )]
+    min_width: Vec<NonZeroUsize>,
+
+    /// Minimum heights of windows.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    #[arg(
+        long,
+        value_name = "NON_ZERO_UINTS",
+        value_delimiter = ',',
+        default_value = "180"
+    )]
+    min_height: Vec<NonZeroUsize>,
+
+    /// Maximum widths of windows.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    /// An empty value means no maximum.
+    #[arg(
+        long,
+        value_name = "NON_ZERO_UINTS",
+        value_parser = non_zero_usize_option_parser,
+        value_delimiter = ',',
+        default_value = "1920"
+    )]
+    max_width: Vec<std::option::Option<NonZeroUsize>>,
+
+    /// Maximum heights of windows.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    /// An empty value means no maximum.
+    #[arg(
+        long,
+        value_name = "NON_ZERO_UINTS",
+        value_parser = non_zero_usize_option_parser,
+        value_delimiter = ',',
+        default_value = ""
+    )]
+    max_height: Vec<std::option::Option<NonZeroUsize>>,
 
-    #[arg(long, value_name = "NON_ZERO_UINT", default_value_t = NonZeroUsize::new(180).unwrap())]
-    min_height: NonZeroUsize,
+    /// Width in pixels
+    /// of a character cell,
+    /// for `--min-columns`.
+    #[arg(long, value_name = "NON_ZERO_UINT", default_value = "8")]
+    cell_width: NonZeroUsize,
 
-    #[arg(long, value_name = "NON_ZERO_UINT", value_parser = non_zero_usize_option_parser, default_value = "1920")]
-    max_width: std::option::Option<NonZeroUsize>,
+    /// Height in pixels
+    /// of a character cell,
+    /// for `--min-rows`.
+    #[arg(long, value_name = "NON_ZERO_UINT", default_value = "16")]
+    cell_height: NonZeroUsize,
 
-    #[arg(long, value_name = "NON_ZERO_UINT", value_parser = non_zero_usize_option_parser, default_value = "")]
-    max_height: std::option::Option<NonZeroUsize>,
+    /// Minimum widths of windows
+    /// in character cells,
+    /// like `80` for a terminal.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    /// Windows are kept at least this wide
+    /// when possible.
+    #[arg(long, value_name = "UINTS", value_delimiter = ',', default_value = "0")]
+    min_columns: Vec<usize>,
+
+    /// Minimum heights of windows
+    /// in character cells,
+    /// like `24` for a terminal.
+    ///
+    /// Values are comma-separated.
+    /// Last value is repeated for further windows.
+    /// Windows are kept at least this tall
+    /// when possible.
+    #[arg(long, value_name = "UINTS", value_delimiter = ',', default_value = "0")]
+    min_rows: Vec<usize>,
+
+    /// Importance of "keep readable" objective,
+    /// penalizing windows smaller than
+    /// `--min-columns` and `--min-rows`
+    /// when they cannot fit.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(3.0).unwrap())]
+    readable_weight: Weight,
 
     /// Set to border thickness
     /// to fully overlap borders.
+    ///
+    /// Cannot be combined with `--inner-gaps`.
     #[arg(long, value_name = "UINT", default_value = "0")]
     overlap_borders_by: usize,
 
+    /// Space between windows.
+    #[arg(long, value_name = "UINT", default_value = "0")]
+    inner_gaps: usize,
+
+    /// Space between windows
+    /// and edges of usable area.
+    #[arg(long, value_name = "UINT", default_value = "0")]
+    outer_gaps: usize,
+
+    /// Leave no space
+    /// around a single window.
+    #[arg(long)]
+    smart_gaps: bool,
+
+    /// How layouts are represented
+    /// during optimization.
+    ///
+    /// One of `free`,
+    /// independent position and size for each window,
+    /// `slicing`,
+    /// recursive splits of usable area
+    /// free of reserved areas,
+    /// never leaving overlap
+    /// or gaps windows can grow into,
+    /// with minimum sizes made a constraint,
+    /// or `grid-span`,
+    /// spans of cells on a grid over usable area
+    /// free of reserved areas,
+    /// ignoring gaps and overlap
+    /// except as objectives,
+    /// with minimum sizes made a constraint,
+    /// see `--span-grid-columns` and `--span-grid-rows`.
+    #[arg(long, value_name = "ENCODING", default_value_t = Encoding::Free)]
+    encoding: Encoding,
+
+    /// Approximate step between encoded positions and sizes
+    /// for `free` and `slicing` encodings.
+    ///
+    /// Value is in pixels,
+    /// or in percent of usable area
+    /// if suffixed by `%`.
+    /// Smaller values allow finer layouts
+    /// but take longer to optimize.
+    #[arg(long, value_name = "LENGTH", default_value_t = Length::Pixels(128))]
+    resolution: Length,
+
+    /// How positions and sizes are represented as bits
+    /// for `free` encoding.
+    ///
+    /// One of `binary`
+    /// or `gray`,
+    /// where adjacent values differ by one bit.
+    #[arg(long, value_name = "CODE", default_value_t = BitCode::Binary)]
+    bit_code: BitCode,
+
+    /// Start optimizing each layout
+    /// near the layout with one less window
+    /// for `free` encoding.
+    ///
+    /// Layouts may converge faster,
+    /// but differ from unseeded layouts.
+    #[arg(long)]
+    seed_from_prev_layout: bool,
+
+    /// Number of grid columns
+    /// for `grid-span` encoding.
+    #[arg(long, value_name = "NON_ZERO_UINT", default_value = "12")]
+    span_grid_columns: NonZeroUsize,
+
+    /// Number of grid rows
+    /// for `grid-span` encoding.
+    #[arg(long, value_name = "NON_ZERO_UINT", default_value = "8")]
+    span_grid_rows: NonZeroUsize,
+
+    /// Region a window is fixed to,
+    /// leaving only other windows to optimize.
+    /// Other windows avoid fixed regions
+    /// like reserved areas.
+    ///
+    /// Of the form `left:WIDTH`,
+    /// full height at the left of usable area,
+    /// or `X,Y,WIDTH,HEIGHT`.
+    /// Values are in pixels,
+    /// or in percent of usable area
+    /// if suffixed by `%`.
+    /// May be given multiple times,
+    /// once for each window,
+    /// starting with the main window.
+    #[arg(long, value_name = "REGION")]
+    template: Vec<Template>,
+
+    /// Requirements layouts must meet,
+    /// if possible,
+    /// regardless of weights.
+    ///
+    /// Values are comma-separated.
+    /// Each value is one of
+    /// `containment`,
+    /// `min-sizes`,
+    /// `size-order`,
+    /// or an objective,
+    /// like `overlap` or `gaps`,
+    /// named as in its `--*-weight` option.
+    /// An objective made a constraint
+    /// is no longer weighted.
+    #[arg(long, value_name = "CONSTRAINTS", value_delimiter = ',')]
+    constraints: Vec<Constraint>,
+
     /// Importance of "minimize gaps" objective.
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(5.0).unwrap())]
     gaps_weight: Weight,
@@ -60,6 +258,18 @@ struct Args {
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(6.0).unwrap())]
     overlap_weight: Weight,
 
+    /// Fraction of a window's area
+    /// below which it counts as hidden
+    /// for "avoid hidden" objective.
+    #[arg(long, value_name = "FRACTION", default_value_t = VisibleFraction::new(0.25).unwrap())]
+    min_visible: VisibleFraction,
+
+    /// Importance of "avoid hidden" objective,
+    /// steeply penalizing windows
+    /// less visible than `--min-visible`.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    hidden_weight: Weight,
+
     /// Desired area ratios between each window and the next.
     ///
     /// Values are comma-separated.
@@ -77,6 +287,13 @@ struct Args {
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(1.5).unwrap())]
     area_ratios_weight: Weight,
 
+    /// Never make a window larger in area
+    /// than the window before it.
+    ///
+    /// Same as adding `size-order` to `--constraints`.
+    #[arg(long)]
+    strict_size_order: bool,
+
     /// Desired aspect ratios of windows.
     ///
     /// Values are comma-separated.
@@ -94,14 +311,72 @@ struct Args {
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(3.0).unwrap())]
     aspect_ratios_weight: Weight,
 
+    /// Groups of windows that should be equal in area
+    /// for "equalize groups" objective.
+    ///
+    /// Values are comma-separated
+    /// ranges of zero-based window indices,
+    /// like `1..` for all windows after the main
+    /// or `1..3` for the second and third.
+    #[arg(
+        long,
+        value_name = "RANGES",
+        value_delimiter = ',',
+        default_value = "1.."
+    )]
+    equal_groups: Vec<IndexGroup>,
+
+    /// Importance of "equalize groups" objective.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    group_equality_weight: Weight,
+
+    /// How to measure closeness
+    /// for "place adjacent close" objective.
+    ///
+    /// One of `corners`,
+    /// distance between nearest corners,
+    /// or `contact`,
+    /// length of shared border.
+    #[arg(long, value_name = "MEASURE", default_value_t = Adjacency::Corners)]
+    adjacency: Adjacency,
+
     /// Importance of "place adjacent close" objective.
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.5).unwrap())]
     adjacent_close_weight: Weight,
 
+    /// Horizontal direction windows are read in
+    /// for "place in reading order" objective.
+    ///
+    /// One of `ltr` or `rtl`.
+    #[arg(long, value_name = "DIRECTION", default_value_t = ReadingDirection::LeftToRight)]
+    reading_direction: ReadingDirection,
+
+    /// Whether to fill rows or columns first
+    /// for "place in reading order" objective.
+    ///
+    /// One of `row`,
+    /// moving back only to start the next row,
+    /// or `column`,
+    /// moving up only to start the next column.
+    #[arg(long, value_name = "MAJOR", default_value_t = ReadingMajor::Row)]
+    reading_major: ReadingMajor,
+
     /// Importance of "place in reading order" objective.
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.5).unwrap())]
     reading_order_weight: Weight,
 
+    /// Where to place the main window
+    /// for "center main" objective.
+    ///
+    /// One of `center`, `left`, `right`, `top`, `bottom`,
+    /// or a point `X,Y`
+    /// the center of the main window should be at.
+    /// Point values are in pixels,
+    /// or in percent of usable area
+    /// if suffixed by `%`.
+    #[arg(long, value_name = "ANCHOR", default_value_t = MainAnchor::Center)]
+    main_anchor: MainAnchor,
+
     /// Importance of "center main" objective.
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(1.5).unwrap())]
     center_main_weight: Weight,
@@ -111,6 +386,202 @@ struct Args {
     /// to the next.
     #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(1.0).unwrap())]
     consistency_weight: Weight,
+
+    /// Axes to mirror windows across
+    /// for "maximize symmetry" objective.
+    ///
+    /// One of `horizontal`, `vertical`, or `both`.
+    #[arg(long, value_name = "AXES", default_value_t = SymmetryAxes::Horizontal)]
+    symmetry_axes: SymmetryAxes,
+
+    /// Importance of "maximize symmetry" objective.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    symmetry_weight: Weight,
+
+    /// Distance within which different window edges
+    /// count as nearly, but not quite, aligned
+    /// for "align edges" objective.
+    #[arg(long, value_name = "UINT", default_value = "16")]
+    edge_alignment_tolerance: usize,
+
+    /// Importance of "align edges" objective.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    edge_alignment_weight: Weight,
+
+    /// Area windows should avoid,
+    /// like a floating video
+    /// or desktop widget.
+    ///
+    /// Of the form `[OUTPUT:]X,Y,WIDTH,HEIGHT`.
+    /// Values are in pixels,
+    /// or in percent of usable area
+    /// if suffixed by `%`.
+    /// Area applies to all outputs
+    /// unless prefixed by an output name.
+    /// May be given multiple times.
+    #[arg(long, value_name = "AREA")]
+    reserved: Vec<OutputReservedRect>,
+
+    /// Importance of "avoid reserved" objective.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(6.0).unwrap())]
+    reserved_weight: Weight,
+
+    /// Desired number of columns
+    /// for "prefer grid" objective.
+    ///
+    /// Values are comma-separated,
+    /// one for each number of windows.
+    /// Last value is repeated for more windows.
+    /// Each value must be `any` or > 0.
+    #[arg(
+        long,
+        value_name = "COUNTS",
+        value_delimiter = ',',
+        default_value = "any"
+    )]
+    grid_columns: Vec<GridCount>,
+
+    /// Desired number of rows
+    /// for "prefer grid" objective.
+    ///
+    /// Values are comma-separated,
+    /// one for each number of windows.
+    /// Last value is repeated for more windows.
+    /// Each value must be `any` or > 0.
+    #[arg(
+        long,
+        value_name = "COUNTS",
+        value_delimiter = ',',
+        default_value = "any"
+    )]
+    grid_rows: Vec<GridCount>,
+
+    /// Importance of "prefer grid" objective.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    grid_weight: Weight,
+
+    /// Importance of "prefer sliceable" objective,
+    /// favoring layouts made by recursively splitting
+    /// the usable area horizontally and vertically.
+    #[arg(long, value_name = "WEIGHT", default_value_t = Weight::new(0.0).unwrap())]
+    sliceable_weight: Weight,
+
+    /// Print layouts trading off weighted objectives
+    /// for a usable area and number of windows,
+    /// then exit,
+    /// instead of running as a layout generator.
+    ///
+    /// Of the form `WIDTH,HEIGHT,COUNT`.
+    /// Each layout is printed
+    /// as a line of `--OBJECTIVE-weight=WEIGHT` arguments
+    /// it was found with,
+    /// a line of `OBJECTIVE=VALUE` pairs,
+    /// lower being better,
+    /// followed by a line of `X,Y,WIDTH,HEIGHT`
+    /// for each window
+    /// and an empty line.
+    /// No layout is better on every objective
+    /// than another printed layout.
+    /// Reserved areas limited to an output are ignored.
+    #[arg(long, value_name = "AREA_AND_COUNT")]
+    pareto_front: Option<ParetoFront>,
+
+    /// Number of weightings to try
+    /// for `--pareto-front`.
+    #[arg(long, value_name = "NON_ZERO_UINT", default_value = "16")]
+    pareto_samples: NonZeroUsize,
+}
+
+/// A command sent by `riverctl send-layout-cmd`.
+#[derive(Clone, Copy, Debug)]
+enum Command {
+    /// Give a window the main window's area and anchor,
+    /// or stop if `None`.
+    Emphasize(Option<usize>),
+    /// Generate a layout again
+    /// after it finished generating.
+    RetryLayout,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["emphasize", "none"] => Ok(Self::Emphasize(None)),
+            ["emphasize", index] => index
+                .parse()
+                .map(|x| Self::Emphasize(Some(x)))
+                .map_err(|e| format!("invalid value '{index}' for 'emphasize <INDEX|none>': {e}")),
+            ["retry-layout"] => Ok(Self::RetryLayout),
+            _ => Err(format!(
+                "'{s}' is not one of 'emphasize <INDEX|none>' or 'retry-layout'"
+            )),
+        }
+    }
+}
+
+/// A usable area and number of windows
+/// to find a Pareto front for.
+#[derive(Clone, Copy, Debug)]
+struct ParetoFront {
+    container: Size,
+    count: NonZeroUsize,
+}
+
+impl FromStr for ParetoFront {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s
+            .split(',')
+            .map(|x| x.parse::<NonZeroUsize>())
+            .collect::<Result<Vec<_>, _>>()
+            .as_deref()
+        {
+            Ok([width, height, count]) => Ok(Self {
+                container: Size::new(*width, *height),
+                count: *count,
+            }),
+            _ => Err(format!(
+                "'{s}' is not of the form `WIDTH,HEIGHT,COUNT` with each value > 0"
+            )),
+        }
+    }
+}
+
+/// A reserved area,
+/// optionally limited to one output.
+#[derive(Clone, Debug)]
+struct OutputReservedRect {
+    output: Option<String>,
+    rect: Region,
+}
+
+impl FromStr for OutputReservedRect {
+    type Err = RegionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((output, rect)) => Ok(Self {
+                output: Some(output.to_owned()),
+                rect: rect.parse()?,
+            }),
+            None => Ok(Self {
+                output: None,
+                rect: s.parse()?,
+            }),
+        }
+    }
+}
+
+impl OutputReservedRect {
+    fn applies_to(&self, output: &str) -> bool {
+        match &self.output {
+            Some(x) => x == output,
+            None => true,
+        }
+    }
 }
 
 fn non_zero_usize_option_parser(
@@ -130,43 +601,152 @@ where
     }
 }
 
+/// Return the `i`th value,
+/// or the last value
+/// if there are not enough values.
+fn nth_or_last<T>(xs: &[T], i: usize) -> T
+where
+    T: Copy,
+{
+    *xs.get(i).or(xs.last()).expect("values should be non-empty")
+}
+
+/// Return an error
+/// if arguments are valid separately
+/// but not together.
+fn validate(args: &Args) -> Result<(), String> {
+    for i in 0..args.min_width.len().max(args.max_width.len()) {
+        let min_width = nth_or_last(&args.min_width, i);
+        if let Some(max_width) = nth_or_last(&args.max_width, i) {
+            if min_width > max_width {
+                return Err(format!("invalid value '{min_width}' at index {i} for '--min-width <NON_ZERO_UINTS>': must be <= value '{max_width}' at index {i} for '--max-width <NON_ZERO_UINTS>'"));
+            }
+        }
+    }
+    for i in 0..args.min_height.len().max(args.max_height.len()) {
+        let min_height = nth_or_last(&args.min_height, i);
+        if let Some(max_height) = nth_or_last(&args.max_height, i) {
+            if min_height > max_height {
+                return Err(format!("invalid value '{min_height}' at index {i} for '--min-height <NON_ZERO_UINTS>': must be <= value '{max_height}' at index {i} for '--max-height <NON_ZERO_UINTS>'"));
+            }
+        }
+    }
+    // Overlapping borders would close inner gaps.
+    let (inner_gaps, overlap_borders_by) = (args.inner_gaps, args.overlap_borders_by);
+    if inner_gaps > 0 && overlap_borders_by > 0 {
+        return Err(format!("invalid value '{inner_gaps}' for '--inner-gaps <UINT>': must be 0 with value '{overlap_borders_by}' for '--overlap-borders-by <UINT>'"));
+    }
+    Ok(())
+}
+
 fn main() {
     let args = Args::parse();
-    if let Some(max_width) = args.max_width {
-        if args.min_width > max_width {
-            eprintln!("error: invalid value '{}' for '--min-width <NON_ZERO_UINT>': must be <= value '{max_width}' for '--max-width <NON_ZERO_UINT>'", args.min_width);
-            std::process::exit(1);
-        }
+    if let Err(e) = validate(&args) {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+
+    let mut constraints = args.constraints;
+    if args.strict_size_order && !constraints.contains(&Constraint::SizeOrder) {
+        constraints.push(Constraint::SizeOrder);
     }
-    if let Some(max_height) = args.max_height {
-        if args.min_height > max_height {
-            eprintln!("error: invalid value '{}' for '--min-height <NON_ZERO_UINT>': must be <= value '{max_height}' for '--max-height <NON_ZERO_UINT>'", args.min_height);
-            std::process::exit(1);
+
+    let gen = LayoutGen::new(LayoutGenParams {
+        min_widths: args.min_width,
+        min_heights: args.min_height,
+        max_widths: args.max_width,
+        max_heights: args.max_height,
+        cell_size: Size::new(args.cell_width, args.cell_height),
+        min_columns: args.min_columns,
+        min_rows: args.min_rows,
+        min_visible: args.min_visible,
+        overlap_borders_by: args.overlap_borders_by,
+        gaps: Gaps {
+            inner: args.inner_gaps,
+            outer: args.outer_gaps,
+            smart: args.smart_gaps,
+        },
+        encoding: args.encoding,
+        resolution: args.resolution,
+        bit_code: args.bit_code,
+        seed_from_prev_layout: args.seed_from_prev_layout,
+        span_grid: Size::new(args.span_grid_columns, args.span_grid_rows),
+        templates: args.template,
+        weights: Weights {
+            gaps_weight: args.gaps_weight,
+            overlap_weight: args.overlap_weight,
+            area_ratios_weight: args.area_ratios_weight,
+            aspect_ratios_weight: args.aspect_ratios_weight,
+            adjacent_close_weight: args.adjacent_close_weight,
+            reading_order_weight: args.reading_order_weight,
+            center_main_weight: args.center_main_weight,
+            consistency_weight: args.consistency_weight,
+            symmetry_weight: args.symmetry_weight,
+            edge_alignment_weight: args.edge_alignment_weight,
+            reserved_weight: args.reserved_weight,
+            grid_weight: args.grid_weight,
+            sliceable_weight: args.sliceable_weight,
+            group_equality_weight: args.group_equality_weight,
+            readable_weight: args.readable_weight,
+            hidden_weight: args.hidden_weight,
+        },
+        constraints,
+        area_ratios: args.area_ratios,
+        aspect_ratios: args.aspect_ratios,
+        equal_groups: args.equal_groups,
+        main_anchor: args.main_anchor,
+        adjacency: args.adjacency,
+        reading_order: ReadingOrder {
+            direction: args.reading_direction,
+            major: args.reading_major,
+        },
+        symmetry_axes: args.symmetry_axes,
+        edge_alignment_tolerance: args.edge_alignment_tolerance,
+        grid_columns: args.grid_columns,
+        grid_rows: args.grid_rows,
+    });
+
+    if let Some(ParetoFront { container, count }) = args.pareto_front {
+        let reserved = args
+            .reserved
+            .iter()
+            .filter(|x| x.output.is_none())
+            .filter_map(|x| x.rect.resolve(container))
+            .collect();
+        for (weights, values, layout) in
+            gen.pareto_front(container, reserved, count, args.pareto_samples)
+        {
+            println!(
+                "{}",
+                weights
+                    .iter()
+                    .map(|(objective, weight)| format!("--{objective}-weight={weight}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            println!(
+                "{}",
+                values
+                    .iter()
+                    .map(|(objective, value)| format!("{objective}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            for rect in layout {
+                println!(
+                    "{},{},{},{}",
+                    rect.x(),
+                    rect.y(),
+                    rect.width(),
+                    rect.height()
+                );
+            }
+            println!();
         }
+        return;
     }
 
-    let mut layout_manager = LayoutManager::new(
-        args.namespace,
-        LayoutGen::new(
-            args.min_width,
-            args.min_height,
-            args.max_width,
-            args.max_height,
-            args.overlap_borders_by,
-            Weights {
-                gaps_weight: args.gaps_weight,
-                overlap_weight: args.overlap_weight,
-                area_ratios_weight: args.area_ratios_weight,
-                aspect_ratios_weight: args.aspect_ratios_weight,
-                adjacent_close_weight: args.adjacent_close_weight,
-                reading_order_weight: args.reading_order_weight,
-                center_main_weight: args.center_main_weight,
-                consistency_weight: args.consistency_weight,
-            },
-            args.area_ratios,
-            args.aspect_ratios,
-        ),
-    );
+    let mut layout_manager = LayoutManager::new(args.namespace, args.reserved, gen);
 
     let conn = Connection::connect_to_env().unwrap();
     let mut event_queue = conn.new_event_queue();
@@ -187,9 +767,17 @@ impl OutputId {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub id: OutputId,
+    pub name: String,
+}
+
 pub struct LayoutManager {
     namespace: String,
+    reserved: Vec<OutputReservedRect>,
     gen: LayoutGen,
+    emphasized: HashMap<OutputId, usize>,
     // These will be initialized
     // by Wayland events.
     seat: Option<Arc<WlSeat>>,
@@ -198,10 +786,12 @@ pub struct LayoutManager {
 }
 
 impl LayoutManager {
-    pub fn new(namespace: String, gen: LayoutGen) -> Self {
+    fn new(namespace: String, reserved: Vec<OutputReservedRect>, gen: LayoutGen) -> Self {
         Self {
             namespace,
+            reserved,
             gen,
+            emphasized: HashMap::new(),
             seat: None,
             manager: None,
             control: None,
@@ -276,7 +866,7 @@ impl Dispatch<WlOutput, ()> for LayoutManager {
         _: &wayland_client::Connection,
         qhandle: &wayland_client::QueueHandle<Self>,
     ) {
-        if let wl_output::Event::Name { name: _ } = event {
+        if let wl_output::Event::Name { name } = event {
             // `get_layout` has necessary side-effects.
             state
                 .manager
@@ -286,18 +876,21 @@ impl Dispatch<WlOutput, ()> for LayoutManager {
                     output,
                     state.namespace.clone(),
                     qhandle,
-                    OutputId::new(output),
+                    Output {
+                        id: OutputId::new(output),
+                        name,
+                    },
                 );
         }
     }
 }
 
-impl Dispatch<RiverLayoutV3, OutputId> for LayoutManager {
+impl Dispatch<RiverLayoutV3, Output> for LayoutManager {
     fn event(
         state: &mut Self,
         proxy: &RiverLayoutV3,
         event: <RiverLayoutV3 as wayland_client::Proxy>::Event,
-        _output: &OutputId,
+        output: &Output,
         conn: &wayland_client::Connection,
         qhandle: &wayland_client::QueueHandle<Self>,
     ) {
@@ -315,7 +908,18 @@ impl Dispatch<RiverLayoutV3, OutputId> for LayoutManager {
                 );
                 let view_count = view_count as usize;
 
-                match state.gen.try_layout(container, view_count) {
+                let reserved = state
+                    .reserved
+                    .iter()
+                    .filter(|x| x.applies_to(&output.name))
+                    .filter_map(|x| x.rect.resolve(container))
+                    .collect::<Vec<_>>();
+                let emphasized = state.emphasized.get(&output.id).copied();
+
+                match state
+                    .gen
+                    .try_layout(container, &reserved, view_count, emphasized)
+                {
                     Status::Finished(layout) => {
                         for rect in layout {
                             proxy.push_view_dimensions(
@@ -342,20 +946,34 @@ impl Dispatch<RiverLayoutV3, OutputId> for LayoutManager {
                             Arc::clone(state.seat.as_ref().expect("seat should be initialized"));
                         let qhandle = qhandle.clone();
                         let conn = conn.clone();
-                        state.gen.layout(container, view_count, move |_| {
-                            // River will send a new layout demand
-                            // if it receives a layout command.
-                            let control = control.lock().unwrap();
-                            control.add_argument("send-layout-cmd".to_owned());
-                            control.add_argument(namespace);
-                            control.add_argument("retry-layout".to_owned());
-                            control.run_command(&seat, &qhandle, ());
-                            let _ = conn.flush();
-                        });
+                        state
+                            .gen
+                            .layout(container, reserved, view_count, emphasized, move |_| {
+                                // River will send a new layout demand
+                                // if it receives a layout command.
+                                let control = control.lock().unwrap();
+                                control.add_argument("send-layout-cmd".to_owned());
+                                control.add_argument(namespace);
+                                control.add_argument("retry-layout".to_owned());
+                                control.run_command(&seat, &qhandle, ());
+                                let _ = conn.flush();
+                            });
                     }
                     Status::Started => {}
                 }
             }
+            // River generates a new layout
+            // after a user command.
+            river_layout_v3::Event::UserCommand { command } => match command.parse() {
+                Ok(Command::Emphasize(Some(index))) => {
+                    state.emphasized.insert(output.id.clone(), index);
+                }
+                Ok(Command::Emphasize(None)) => {
+                    state.emphasized.remove(&output.id);
+                }
+                Ok(Command::RetryLayout) => {}
+                Err(e) => eprintln!("error: {e}"),
+            },
             river_layout_v3::Event::NamespaceInUse => {
                 panic!(
                     "namespace '{}' in use: layout program may already be running",
@@ -431,3 +1049,17 @@ mod protocol {
     wayland_scanner::generate_client_code!("./protocols/river-layout-v3.xml");
     wayland_scanner::generate_client_code!("./protocols/river-control-unstable-v1.xml");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_rejects_inner_gaps_with_overlapping_borders() {
+        let args = |xs: &[&str]| Args::parse_from(["owm"].iter().chain(xs));
+        assert!(validate(&args(&["--inner-gaps", "4", "--overlap-borders-by", "2"])).is_err());
+        assert!(validate(&args(&["--inner-gaps", "4"])).is_ok());
+        assert!(validate(&args(&["--overlap-borders-by", "2"])).is_ok());
+        assert!(validate(&args(&["--outer-gaps", "4", "--overlap-borders-by", "2"])).is_ok());
+    }
+}
@@ -1,99 +1,252 @@
+mod pbil;
+
 use std::{
     collections::hash_map::{Entry, HashMap},
+    iter::repeat,
     num::NonZeroUsize,
     sync::Arc,
     thread,
 };
 
+use ndarray::prelude::*;
 use once_cell::sync::OnceCell;
-use optimal::{optimizer::derivative_free::pbil::*, prelude::*};
 use owm_problem::{
-    encoding::Decoder, objective::Problem, post_processing::overlap_borders, AreaRatio,
-    AspectRatio, Rect, Size, Weights,
+    encoding::{
+        seed_probabilities, BitCode, Decode, Decoder, Encoding, GridSpanDecoder, SlicingDecoder,
+        Template, TemplateDecoder,
+    },
+    objective::{Objective, Problem, ProblemParams},
+    post_processing::{add_gaps, overlap_borders, Gaps},
+    Adjacency, AreaRatio, AspectRatio, Constraint, GridCount, IndexGroup, Length, MainAnchor,
+    ReadingOrder, Rect, Size, SymmetryAxes, VisibleFraction, Weight, Weights,
 };
 use rand::prelude::*;
 use rand_xoshiro::SplitMix64;
 use rayon::prelude::*;
 
+use crate::pbil::Pbil;
+
+/// How far the optimizer starts
+/// from uniform probabilities
+/// toward a seed layout,
+/// in `0..=0.5`.
+const SEED_BIAS: f64 = 0.1;
+
 #[derive(Debug)]
 pub struct LayoutGen {
-    inner: Arc<RawLayoutGen>,
+    inner: Arc<LayoutGenParams>,
     cache: HashMap<Key, Arc<OnceCell<Vec<Rect>>>>,
 }
 
+/// Settings shared by every layout
+/// a `LayoutGen` makes.
 #[derive(Clone, Debug)]
-struct RawLayoutGen {
-    min_width: NonZeroUsize,
-    min_height: NonZeroUsize,
-    max_width: Option<NonZeroUsize>,
-    max_height: Option<NonZeroUsize>,
-    overlap_borders_by: usize,
-    weights: Weights,
-    area_ratios: Vec<AreaRatio>,
-    aspect_ratios: Vec<AspectRatio>,
+pub struct LayoutGenParams {
+    pub min_widths: Vec<NonZeroUsize>,
+    pub min_heights: Vec<NonZeroUsize>,
+    pub max_widths: Vec<Option<NonZeroUsize>>,
+    pub max_heights: Vec<Option<NonZeroUsize>>,
+    pub cell_size: Size,
+    pub min_columns: Vec<usize>,
+    pub min_rows: Vec<usize>,
+    pub min_visible: VisibleFraction,
+    pub overlap_borders_by: usize,
+    pub gaps: Gaps,
+    pub encoding: Encoding,
+    pub resolution: Length,
+    pub bit_code: BitCode,
+    /// Whether optimizing `free` layouts
+    /// starts near the layout with one less window,
+    /// instead of from uniform probabilities.
+    pub seed_from_prev_layout: bool,
+    pub span_grid: Size,
+    pub templates: Vec<Template>,
+    pub weights: Weights,
+    pub constraints: Vec<Constraint>,
+    pub area_ratios: Vec<AreaRatio>,
+    pub aspect_ratios: Vec<AspectRatio>,
+    pub equal_groups: Vec<IndexGroup>,
+    pub main_anchor: MainAnchor,
+    pub adjacency: Adjacency,
+    pub reading_order: ReadingOrder,
+    pub symmetry_axes: SymmetryAxes,
+    pub edge_alignment_tolerance: usize,
+    pub grid_columns: Vec<GridCount>,
+    pub grid_rows: Vec<GridCount>,
 }
 
-type Key = (Size, usize);
+/// Usable area, reserved areas, number of windows,
+/// and emphasized window.
+type Key = (Size, Vec<Rect>, usize, Option<usize>);
 
-pub enum Status<'a> {
+/// A layout,
+/// the weight each objective it was compared on
+/// was optimized with,
+/// and the unweighted value of each such objective.
+pub type LabeledLayout = (Vec<(Objective, Weight)>, Vec<(Objective, f64)>, Vec<Rect>);
+
+pub enum Status {
     NotStarted,
     Started,
-    Finished(&'a [Rect]),
+    Finished(Vec<Rect>),
 }
 
 impl LayoutGen {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        min_width: NonZeroUsize,
-        min_height: NonZeroUsize,
-        max_width: Option<NonZeroUsize>,
-        max_height: Option<NonZeroUsize>,
-        overlap_borders_by: usize,
-        weights: Weights,
-        area_ratios: Vec<AreaRatio>,
-        aspect_ratios: Vec<AspectRatio>,
-    ) -> Self {
+    pub fn new(params: LayoutGenParams) -> Self {
         Self {
-            inner: Arc::new(RawLayoutGen {
-                min_width,
-                min_height,
-                max_width,
-                max_height,
-                overlap_borders_by,
-                weights,
-                area_ratios,
-                aspect_ratios,
-            }),
+            inner: Arc::new(params),
             cache: HashMap::new(),
         }
     }
 
-    pub fn try_layout(&self, container: Size, count: usize) -> Status {
-        match self.cache.get(&(container, count)) {
+    /// `reserved` areas are avoided
+    /// and distinguish otherwise equal layouts.
+    /// An `emphasized` window
+    /// is given the main window's area and anchor
+    /// without reordering windows,
+    /// and its layouts are cached separately.
+    pub fn try_layout(
+        &self,
+        container: Size,
+        reserved: &[Rect],
+        count: usize,
+        emphasized: Option<usize>,
+    ) -> Status {
+        match self
+            .cache
+            .get(&key(container, reserved.to_vec(), count, emphasized))
+        {
             Some(cache_cell) => match cache_cell.get() {
-                Some(layout) => Status::Finished(layout),
+                Some(layout) => {
+                    let mut layout = layout.clone();
+                    self.inner.post_process(container, &mut layout);
+                    Status::Finished(layout)
+                }
                 None => Status::Started,
             },
             None => Status::NotStarted,
         }
     }
 
-    pub fn layout<F>(&mut self, container: Size, count: usize, callback: F)
-    where
+    /// Return layouts
+    /// no other found layout is better than
+    /// on every weighted objective,
+    /// for exploring tradeoffs between objectives.
+    ///
+    /// Layouts are found by optimizing
+    /// configured weights
+    /// and `samples - 1` random weightings
+    /// of objectives with non-zero weight.
+    /// Objectives made constraints are not compared.
+    /// Layouts are not cached.
+    pub fn pareto_front(
+        &self,
+        container: Size,
+        reserved: Vec<Rect>,
+        count: NonZeroUsize,
+        samples: NonZeroUsize,
+    ) -> Vec<LabeledLayout> {
+        let objectives = Objective::ALL
+            .into_iter()
+            .filter(|objective| {
+                self.inner.weights.get(*objective) > Weight::new(0.0).unwrap()
+                    && !self
+                        .inner
+                        .constraints
+                        .contains(&Constraint::Objective(*objective))
+            })
+            .collect::<Vec<_>>();
+
+        let prev_layout = (1..count.get()).fold(Vec::new(), |prev_layout, _| {
+            self.inner
+                .optimize(container, reserved.clone(), 0, prev_layout)
+        });
+        let (_, problem, _) =
+            self.inner
+                .decoder_and_problem(container, reserved.clone(), 0, prev_layout.clone());
+
+        let mut rng = SplitMix64::seed_from_u64(0);
+        let mut front: Vec<LabeledLayout> = Vec::new();
+        for sample in 0..samples.get() {
+            let mut weights = self.inner.weights;
+            if sample > 0 {
+                // Exponentially distributed weights
+                // are uniform over normalized weightings.
+                for objective in &objectives {
+                    *weights.get_mut(*objective) =
+                        Weight::new(-(1.0 - rng.gen::<f64>()).ln()).unwrap();
+                }
+            }
+            let rects = LayoutGenParams {
+                weights,
+                ..LayoutGenParams::clone(&self.inner)
+            }
+            .optimize(container, reserved.clone(), 0, prev_layout.clone());
+            let values = problem
+                .evaluate_each(&rects)
+                .into_iter()
+                .filter(|(objective, _)| objectives.contains(objective))
+                .collect::<Vec<_>>();
+            if front
+                .iter()
+                .all(|(_, other, other_rects)| *other_rects != rects && !dominates(other, &values))
+            {
+                front.retain(|(_, other, _)| !dominates(&values, other));
+                front.push((
+                    objectives
+                        .iter()
+                        .map(|objective| (*objective, weights.get(*objective)))
+                        .collect(),
+                    values,
+                    rects,
+                ));
+            }
+        }
+
+        for (_, _, rects) in front.iter_mut() {
+            self.inner.post_process(container, rects);
+        }
+        front
+    }
+
+    pub fn layout<F>(
+        &mut self,
+        container: Size,
+        reserved: Vec<Rect>,
+        count: usize,
+        emphasized: Option<usize>,
+        callback: F,
+    ) where
         F: FnOnce(&[Rect]) + Send + 'static,
     {
-        self._layout(container, count, Box::new(callback))
+        let gen = Arc::clone(&self.inner);
+        self._layout(
+            container,
+            reserved,
+            count,
+            emphasized,
+            Box::new(move |layout: &[Rect]| {
+                let mut layout = layout.to_vec();
+                gen.post_process(container, &mut layout);
+                (callback)(&layout)
+            }),
+        )
     }
 
+    // Layouts are cached before post-processing,
+    // so they can be compared to the next layout.
     // `Box` avoids infinite recusion during compilation.
     #[allow(clippy::type_complexity)]
     fn _layout(
         &mut self,
         container: Size,
+        reserved: Vec<Rect>,
         count: usize,
+        emphasized: Option<usize>,
         callback: Box<dyn FnOnce(&[Rect]) + Send + 'static>,
     ) {
-        let key = (container, count);
+        let key = key(container, reserved.clone(), count, emphasized);
+        let main = key.3.unwrap_or(0);
         if count == 0 {
             return (callback)(
                 self.cache
@@ -106,13 +259,15 @@ impl LayoutGen {
             Entry::Vacant(entry) => {
                 let cache_cell = Arc::clone(entry.insert(Arc::new(OnceCell::new())));
                 let gen = Arc::clone(&self.inner);
-                self.layout(
+                self._layout(
                     container,
+                    reserved.clone(),
                     count - 1,
+                    emphasized,
                     Box::new(move |prev_layout: &[Rect]| {
                         let prev_layout = prev_layout.to_vec();
                         thread::spawn(move || {
-                            let layout = gen.layout(container, prev_layout);
+                            let layout = gen.optimize(container, reserved, main, prev_layout);
                             let layout = cache_cell
                                 .try_insert(layout)
                                 .expect("cell should be unset for {key:?}");
@@ -134,69 +289,383 @@ impl LayoutGen {
     }
 }
 
-impl RawLayoutGen {
-    fn layout(&self, container: Size, prev_layout: Vec<Rect>) -> Vec<Rect> {
+impl LayoutGenParams {
+    fn post_process(&self, container: Size, rects: &mut [Rect]) {
+        add_gaps(self.gaps, container, rects);
+        if self.overlap_borders_by > 0 {
+            overlap_borders(self.overlap_borders_by, container, rects);
+        }
+    }
+
+    /// Return the best layout found,
+    /// before post-processing.
+    /// `main` is the index of the window
+    /// given the main window's area and anchor.
+    fn optimize(
+        &self,
+        container: Size,
+        reserved: Vec<Rect>,
+        main: usize,
+        prev_layout: Vec<Rect>,
+    ) -> Vec<Rect> {
         let count = prev_layout.len() + 1;
+        if self.templates.len() >= count {
+            // Every window is fixed,
+            // so there is nothing to optimize.
+            return self.fixed(container, count);
+        }
+        let (decoder, problem, probabilities) =
+            self.decoder_and_problem(container, reserved, main, prev_layout);
+        optimize(decoder.as_ref(), &problem, probabilities)
+    }
+
+    /// Return a decoder,
+    /// a problem,
+    /// and probabilities to start optimizing from,
+    /// biased toward `prev_layout`
+    /// and one more window
+    /// when seeding is enabled
+    /// and the encoding allows.
+    fn decoder_and_problem(
+        &self,
+        container: Size,
+        reserved: Vec<Rect>,
+        main: usize,
+        prev_layout: Vec<Rect>,
+    ) -> (Box<dyn Decode + Sync>, Problem, Array1<f64>) {
+        let count = prev_layout.len() + 1;
+        let max_sizes = repeat_last(&self.max_widths)
+            .zip(repeat_last(&self.max_heights))
+            .map(|(width, height)| {
+                Size::new(
+                    width.map_or(container.width, |x| x.min(container.width)),
+                    height.map_or(container.height, |x| x.min(container.height)),
+                )
+            })
+            .take(count)
+            .collect::<Vec<_>>();
+        let readable_sizes = repeat_last(&self.min_columns)
+            .zip(repeat_last(&self.min_rows))
+            .map(|(columns, rows)| {
+                Size::new_checked(
+                    (columns * self.cell_size.width.get()).max(1),
+                    (rows * self.cell_size.height.get()).max(1),
+                )
+            })
+            .take(count)
+            .collect::<Vec<_>>();
+        // Readable sizes may not fit,
+        // so they are also an objective.
+        let min_sizes = repeat_last(&self.min_widths)
+            .zip(repeat_last(&self.min_heights))
+            .zip(readable_sizes.iter().zip(&max_sizes))
+            .map(|((width, height), (readable_size, max_size))| {
+                Size::new(
+                    width.max(readable_size.width).min(max_size.width),
+                    height.max(readable_size.height).min(max_size.height),
+                )
+            })
+            .collect::<Vec<_>>();
+        // Objectives only need an upper bound
+        // on window size.
         let max_size = Size::new(
-            self.max_width
-                .map_or(container.width, |x| x.min(container.width)),
-            self.max_height
-                .map_or(container.height, |x| x.min(container.height)),
+            max_sizes.iter().map(|x| x.width).max().unwrap(),
+            max_sizes.iter().map(|x| x.height).max().unwrap(),
         );
-        let decoder = Decoder::new(
-            Size::new(
-                self.min_width.min(container.width),
-                self.min_height.min(container.height),
+        let fixed = self.fixed(container, count);
+        // Other windows avoid fixed windows
+        // like reserved areas.
+        let obstacles = reserved.iter().chain(&fixed).copied().collect::<Vec<_>>();
+        let (decoder, seed): (Box<dyn Decode + Sync>, _) = match self.encoding {
+            Encoding::Free => {
+                let decoder = Decoder::new(
+                    min_sizes[fixed.len()..].to_vec(),
+                    max_sizes[fixed.len()..].to_vec(),
+                    self.resolution,
+                    self.bit_code,
+                    obstacles,
+                    container,
+                );
+                // The new window starts
+                // where the last window is,
+                // leaving the optimizer to move them apart.
+                let seed = prev_layout[fixed.len()..]
+                    .last()
+                    .filter(|_| self.seed_from_prev_layout)
+                    .map(|last| {
+                        decoder.encode(
+                            &prev_layout[fixed.len()..]
+                                .iter()
+                                .chain([last])
+                                .copied()
+                                .collect::<Vec<_>>(),
+                        )
+                    });
+                (Box::new(decoder), seed)
+            }
+            Encoding::Slicing => (
+                Box::new(SlicingDecoder::new(
+                    max_sizes[fixed.len()..].to_vec(),
+                    self.resolution,
+                    obstacles,
+                    container,
+                )),
+                None,
             ),
+            Encoding::GridSpan => (
+                Box::new(GridSpanDecoder::new(
+                    max_sizes[fixed.len()..].to_vec(),
+                    self.span_grid,
+                    &obstacles,
+                    container,
+                )),
+                None,
+            ),
+        };
+        let probabilities = match seed {
+            Some(bits) => seed_probabilities(bits.view(), SEED_BIAS),
+            None => Array1::from_elem(decoder.bits(), 0.5),
+        };
+        let mut constraints = self.constraints.clone();
+        if self.encoding != Encoding::Free && !constraints.contains(&Constraint::MinSizes) {
+            // Only the free encoding
+            // keeps windows at least their minimum size,
+            // so other layouts are required to.
+            constraints.push(Constraint::MinSizes);
+        }
+        // Fixed windows cannot grow
+        // to their minimum size.
+        let min_sizes = min_sizes
+            .into_iter()
+            .enumerate()
+            .map(|(i, min_size)| match fixed.get(i) {
+                Some(rect) => Size::new(
+                    min_size.width.min(rect.width()),
+                    min_size.height.min(rect.height()),
+                ),
+                None => min_size,
+            })
+            .collect();
+        let problem = Problem::new(ProblemParams {
+            weights: self.weights,
+            constraints,
+            area_ratios: self.area_ratios.clone(),
+            aspect_ratios: self.aspect_ratios.clone(),
+            equal_groups: self.equal_groups.clone(),
+            main_anchor: self.main_anchor,
+            main,
+            adjacency: self.adjacency,
+            reading_order: self.reading_order,
+            symmetry_axes: self.symmetry_axes,
+            edge_alignment_tolerance: self.edge_alignment_tolerance,
+            grid_columns: repeat_last(&self.grid_columns).nth(count - 1).unwrap(),
+            grid_rows: repeat_last(&self.grid_rows).nth(count - 1).unwrap(),
+            readable_sizes,
+            min_visible: self.min_visible,
+            min_sizes,
             max_size,
             container,
-            count,
-        );
-        let problem = Problem::new(
-            self.weights,
-            self.area_ratios.clone(),
-            self.aspect_ratios.clone(),
-            max_size,
-            container,
+            reserved,
             prev_layout,
-        );
-        let mut rects = decoder
-            .decode1(
-                UntilConvergedConfig {
-                    threshold: ProbabilityThreshold::new(Probability::new(0.9).unwrap()).unwrap(),
-                }
-                .argmin(
-                    &mut Config {
-                        num_samples: NumSamples::new(
-                            500 * std::thread::available_parallelism().map_or(1, |x| x.into()),
-                        )
-                        .unwrap(),
-                        adjust_rate: AdjustRate::new(0.1).unwrap(),
-                        mutation_chance: MutationChance::new(0.0).unwrap(),
-                        mutation_adjust_rate: MutationAdjustRate::new(0.05).unwrap(),
-                    }
-                    .start_using(
-                        decoder.bits(),
-                        |points| {
-                            (0..points.nrows())
-                                .into_par_iter()
-                                .map(|i| {
-                                    problem.evaluate(
-                                        decoder.decode1(points.row(i)).as_slice().unwrap(),
-                                    )
-                                })
-                                .collect::<Vec<_>>()
-                                .into()
-                        },
-                        &mut SplitMix64::seed_from_u64(0),
-                    ),
-                )
-                .view(),
+        });
+        let decoder: Box<dyn Decode + Sync> = if fixed.is_empty() {
+            decoder
+        } else {
+            Box::new(TemplateDecoder::new(fixed, decoder))
+        };
+        (decoder, problem, probabilities)
+    }
+
+    /// Return regions of windows
+    /// fixed by templates.
+    fn fixed(&self, container: Size, count: usize) -> Vec<Rect> {
+        self.templates
+            .iter()
+            .take(count)
+            .map(|x| x.resolve(container))
+            .collect()
+    }
+}
+
+/// Return the best layout `decoder` can decode
+/// for `problem`,
+/// starting from `probabilities`
+/// of each bit being `true`.
+pub fn optimize(
+    decoder: &(dyn Decode + Sync),
+    problem: &Problem,
+    probabilities: Array1<f64>,
+) -> Vec<Rect> {
+    decoder
+        .decode1(
+            Pbil {
+                num_samples: 500 * std::thread::available_parallelism().map_or(1, |x| x.into()),
+                adjust_rate: 0.1,
+                threshold: 0.9,
+            }
+            .argmin(
+                probabilities,
+                |points| {
+                    (0..points.nrows())
+                        .into_par_iter()
+                        .map(|i| {
+                            problem.evaluate(decoder.decode1(points.row(i)).as_slice().unwrap())
+                        })
+                        .collect::<Vec<_>>()
+                        .into()
+                },
+                &mut SplitMix64::seed_from_u64(0),
             )
-            .into_raw_vec();
-        if self.overlap_borders_by > 0 {
-            overlap_borders(self.overlap_borders_by, container, &mut rects);
+            .view(),
+        )
+        .into_raw_vec()
+}
+
+/// Return a cache key,
+/// ignoring emphasis of a window
+/// not in the layout
+/// or already main.
+fn key(container: Size, reserved: Vec<Rect>, count: usize, emphasized: Option<usize>) -> Key {
+    (
+        container,
+        reserved,
+        count,
+        emphasized.filter(|i| (1..count).contains(i)),
+    )
+}
+
+/// Return whether `xs` is no worse than `ys`
+/// on every objective
+/// and better on at least one.
+fn dominates(xs: &[(Objective, f64)], ys: &[(Objective, f64)]) -> bool {
+    xs.iter().zip(ys).all(|((_, x), (_, y))| x <= y)
+        && xs.iter().zip(ys).any(|((_, x), (_, y))| x < y)
+}
+
+/// Return an iterator over `xs`
+/// repeating the last value forever.
+fn repeat_last<T>(xs: &[T]) -> impl Iterator<Item = T> + '_
+where
+    T: Copy,
+{
+    xs.iter()
+        .chain(repeat(xs.last().expect("values should be non-empty")))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoder_and_problem_starts_from_uniform_probabilities_by_default() {
+        let container = Size::new_checked(64, 64);
+        let params = params(false);
+        let prev_layout = params.optimize(container, Vec::new(), 0, Vec::new());
+        let (_, _, probabilities) =
+            params.decoder_and_problem(container, Vec::new(), 0, prev_layout);
+        assert!(probabilities.iter().all(|x| *x == 0.5))
+    }
+
+    #[test]
+    fn decoder_and_problem_biases_probabilities_when_seeding() {
+        let container = Size::new_checked(64, 64);
+        let params = params(true);
+        let prev_layout = params.optimize(container, Vec::new(), 0, Vec::new());
+        let (_, _, probabilities) =
+            params.decoder_and_problem(container, Vec::new(), 0, prev_layout);
+        assert!(probabilities.iter().all(|x| *x != 0.5))
+    }
+
+    #[test]
+    fn decoder_and_problem_requires_min_sizes_unless_free() {
+        let container = Size::new_checked(64, 64);
+        for encoding in [Encoding::Slicing, Encoding::GridSpan] {
+            let params = LayoutGenParams {
+                min_widths: vec![NonZeroUsize::new(16).unwrap()],
+                encoding,
+                ..params(false)
+            };
+            let (_, problem, _) = params.decoder_and_problem(
+                container,
+                Vec::new(),
+                0,
+                vec![Rect::new_checked(0, 0, 64, 64)],
+            );
+            // Every objective has weight `1`,
+            // so feasible layouts are at most `Objective::ALL.len()`.
+            assert!(
+                problem.evaluate(&[
+                    Rect::new_checked(0, 0, 60, 64),
+                    Rect::new_checked(60, 0, 4, 64)
+                ]) > Objective::ALL.len() as f64
+            );
+            assert!(
+                problem.evaluate(&[
+                    Rect::new_checked(0, 0, 32, 64),
+                    Rect::new_checked(32, 0, 32, 64)
+                ]) <= Objective::ALL.len() as f64
+            )
+        }
+    }
+
+    #[test]
+    fn optimize_without_prev_layout_ignores_seeding() {
+        let container = Size::new_checked(64, 64);
+        assert_eq!(
+            params(true).optimize(container, Vec::new(), 0, Vec::new()),
+            params(false).optimize(container, Vec::new(), 0, Vec::new())
+        )
+    }
+
+    fn params(seed_from_prev_layout: bool) -> LayoutGenParams {
+        let weight = Weight::new(1.0).unwrap();
+        LayoutGenParams {
+            min_widths: vec![NonZeroUsize::MIN],
+            min_heights: vec![NonZeroUsize::MIN],
+            max_widths: vec![None],
+            max_heights: vec![None],
+            cell_size: Size::new_checked(1, 1),
+            min_columns: vec![0],
+            min_rows: vec![0],
+            min_visible: VisibleFraction::new(0.25).unwrap(),
+            overlap_borders_by: 0,
+            gaps: Gaps::default(),
+            encoding: Encoding::Free,
+            resolution: Length::Pixels(16),
+            bit_code: BitCode::Binary,
+            seed_from_prev_layout,
+            span_grid: Size::new_checked(4, 4),
+            templates: Vec::new(),
+            weights: Weights {
+                gaps_weight: weight,
+                overlap_weight: weight,
+                area_ratios_weight: weight,
+                aspect_ratios_weight: weight,
+                adjacent_close_weight: weight,
+                reading_order_weight: weight,
+                center_main_weight: weight,
+                consistency_weight: weight,
+                symmetry_weight: weight,
+                edge_alignment_weight: weight,
+                reserved_weight: weight,
+                grid_weight: weight,
+                sliceable_weight: weight,
+                group_equality_weight: weight,
+                readable_weight: weight,
+                hidden_weight: weight,
+            },
+            constraints: Vec::new(),
+            area_ratios: vec![AreaRatio::new(2.0).unwrap()],
+            aspect_ratios: vec![AspectRatio::new(1.0).unwrap()],
+            equal_groups: Vec::new(),
+            main_anchor: MainAnchor::Center,
+            adjacency: Adjacency::Corners,
+            reading_order: ReadingOrder::default(),
+            symmetry_axes: SymmetryAxes::Horizontal,
+            edge_alignment_tolerance: 0,
+            grid_columns: vec![GridCount::Any],
+            grid_rows: vec![GridCount::Any],
         }
-        rects
     }
 }
@@ -0,0 +1,121 @@
+use ndarray::prelude::*;
+use rand::prelude::*;
+
+/// Population-based incremental learning,
+/// a derivative-free optimizer over bits
+/// that can start from any probabilities,
+/// like those biased toward a known good point.
+///
+/// This runs like `optimal`'s PBIL until converged,
+/// as previously configured,
+/// but does not always start from uniform probabilities.
+/// Mutation is left out
+/// because its chance was `0`.
+#[derive(Clone, Copy, Debug)]
+pub struct Pbil {
+    /// Points sampled each step.
+    pub num_samples: usize,
+    /// How far probabilities move
+    /// toward the best sample
+    /// each step.
+    pub adjust_rate: f64,
+    /// How close to `0` or `1`
+    /// every probability must be
+    /// to stop.
+    pub threshold: f64,
+}
+
+impl Pbil {
+    /// Return the most likely point
+    /// once every probability has converged,
+    /// starting from `probabilities`
+    /// of each bit being `true`.
+    ///
+    /// `f` evaluates each row of points,
+    /// lower being better.
+    pub fn argmin<F, R>(&self, mut probabilities: Array1<f64>, f: F, rng: &mut R) -> Array1<bool>
+    where
+        F: Fn(ArrayView2<bool>) -> Array1<f64>,
+        R: Rng,
+    {
+        while !probabilities
+            .iter()
+            .all(|p| *p >= self.threshold || *p <= 1.0 - self.threshold)
+        {
+            let points =
+                Array2::from_shape_fn((self.num_samples, probabilities.len()), |(_, j)| {
+                    rng.gen_bool(probabilities[j])
+                });
+            let best = f(points.view())
+                .iter()
+                .enumerate()
+                .min_by(|(_, x), (_, y)| x.total_cmp(y))
+                .map_or(0, |(i, _)| i);
+            for (p, bit) in probabilities.iter_mut().zip(points.row(best)) {
+                *p += self.adjust_rate * (if *bit { 1.0 } else { 0.0 } - *p);
+            }
+        }
+        probabilities.map(|p| *p >= 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use owm_problem::encoding::seed_probabilities;
+    use rand_xoshiro::SplitMix64;
+
+    use super::*;
+
+    #[test]
+    fn pbil_minimizes_count_of_true_bits() {
+        assert_eq!(
+            pbil().argmin(
+                Array1::from_elem(8, 0.5),
+                count_true,
+                &mut SplitMix64::seed_from_u64(0)
+            ),
+            Array1::from_elem(8, false)
+        );
+    }
+
+    #[test]
+    fn pbil_returns_converged_probabilities_without_sampling() {
+        assert_eq!(
+            pbil().argmin(
+                array![0.95, 0.05],
+                |_| panic!("should not sample"),
+                &mut SplitMix64::seed_from_u64(0)
+            ),
+            array![true, false]
+        );
+    }
+
+    #[test]
+    fn pbil_converges_from_unbiased_seed_like_uniform_start() {
+        let seed = Array1::from_iter((0..8).map(|i| i % 3 == 0));
+        assert_eq!(
+            pbil().argmin(
+                seed_probabilities(seed.view(), 0.0),
+                count_true,
+                &mut SplitMix64::seed_from_u64(0)
+            ),
+            pbil().argmin(
+                Array1::from_elem(8, 0.5),
+                count_true,
+                &mut SplitMix64::seed_from_u64(0)
+            )
+        );
+    }
+
+    fn pbil() -> Pbil {
+        Pbil {
+            num_samples: 20,
+            adjust_rate: 0.1,
+            threshold: 0.9,
+        }
+    }
+
+    fn count_true(points: ArrayView2<bool>) -> Array1<f64> {
+        points.map_axis(Axis(1), |row| row.iter().filter(|x| **x).count() as f64)
+    }
+}